@@ -0,0 +1,81 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Options given to the `#[mutation_test]` attribute
+
+use syntax::ast;
+use syntax::attr::AttrMetaMethods;
+use syntax::ext::base::ExtCtxt;
+
+/// Options which control how mutants are made
+pub struct Config {
+    /// Whether to add iteration guards to loops, so that they can be mutated
+    pub guard_loops: bool,
+    /// Method renames for MethodSwap to do in addition to its defaults
+    pub swap_methods: Vec<(String, String)>,
+    /// Methods for MethodDelete to remove in addition to its defaults
+    pub identity_methods: Vec<String>,
+    /// Whether OverflowSwap should replace every `+` by `wrapping_add`,
+    /// not just those whose operands' integer type is written out
+    pub wrap_plus: bool
+}
+
+impl Config {
+    /// Create a new Config with all options at their defaults
+    pub fn new() -> Config {
+        Config {
+            guard_loops: false,
+            swap_methods: vec![],
+            identity_methods: vec![],
+            wrap_plus: false
+        }
+    }
+
+    /// Read the options from a `#[mutation_test(...)]` attribute, e.g.
+    /// `#[mutation_test(guard_loops, swap_methods(lock = "try_lock"),
+    /// identity_methods(normalize))]`,
+    /// warning about any that we do not understand
+    pub fn from_meta(cx: &mut ExtCtxt, meta: &ast::MetaItem) -> Config {
+        let mut config = Config::new();
+        let options = match meta.meta_item_list() {
+            Some(options) => options,
+            None => return config
+        };
+        for option in options.iter() {
+            match &*option.name() {
+                "guard_loops" => config.guard_loops = true,
+                "wrap_plus" => config.wrap_plus = true,
+                "swap_methods" => {
+                    for pair in option.meta_item_list().unwrap_or(&[]).iter() {
+                        match pair.value_str() {
+                            Some(to) => config.swap_methods.push((pair.name().to_string(), to.to_string())),
+                            None => cx.span_warn(pair.span, "expected `from = \"to\"` in swap_methods")
+                        }
+                    }
+                },
+                "identity_methods" => {
+                    for name in option.meta_item_list().unwrap_or(&[]).iter() {
+                        match name.node {
+                            ast::MetaItem_::MetaWord(ref name) => config.identity_methods.push(name.to_string()),
+                            _ => cx.span_warn(name.span, "expected a method name in identity_methods")
+                        }
+                    }
+                },
+                _ => cx.span_warn(option.span, "unknown #[mutation_test] option")
+            }
+        }
+        config
+    }
+}
+
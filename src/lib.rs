@@ -27,6 +27,7 @@ use syntax::ext::base::ExtCtxt;
 use syntax::fold::Folder;
 use syntax::ptr::P;
 
+mod config;
 mod locator;
 mod mutator;
 mod test_duper;
@@ -80,9 +81,10 @@ pub fn expand_mutate(cx: &mut ExtCtxt, decorator_span: Span,
     item
 }
 
-/// This annotation should only be applied to modules
+/// This annotation should only be applied to modules. It may be given
+/// options, e.g. `#[mutation_test(guard_loops)]`; see `config::Config`.
 pub fn expand_mutation_test(cx: &mut ExtCtxt, decorator_span: Span,
-                            _meta: &ast::MetaItem, item: Annotatable)
+                            meta: &ast::MetaItem, item: Annotatable)
                            -> Annotatable {
 
     // Ensure that we are actually looking at a module
@@ -110,7 +112,8 @@ pub fn expand_mutation_test(cx: &mut ExtCtxt, decorator_span: Span,
     // At this point we know we are looking at a module
     // Run through the module duplicating and marring annotated functions
     let item = item.expect_item();
-    let mut loc = locator::Locator::new();
+    let config = config::Config::from_meta(cx, meta);
+    let mut loc = locator::Locator::new(config);
     let item = loc.fold_item_simple((*item).clone());
 
     // Add new unit tests...
@@ -18,11 +18,14 @@ use syntax::ext::base::Annotatable;
 use syntax::ptr::P;
 use syntax::util::small_vector::SmallVector;
 
+use config::Config;
 use mutator;
 use util;
 
 pub struct Locator {
     last_path: Vec<ast::PathSegment>,
+    /// Options from the `#[mutation_test]` attribute
+    config: Config,
     /// A mapping from function names to lists of names of their mutated
     /// variants
     pub name_mappings: HashMap<ast::Ident, Vec<Vec<ast::PathSegment>>>,
@@ -30,9 +33,10 @@ pub struct Locator {
 
 impl Locator {
     /// Create a new Mutator
-    pub fn new() -> Locator {
+    pub fn new(config: Config) -> Locator {
         Locator {
             last_path: vec![],
+            config: config,
             name_mappings: HashMap::new(),
         }
     }
@@ -46,9 +50,8 @@ impl fold::Folder for Locator {
                 // Is this a function that we want to make mutated copies of?
                 if attr::contains_name(&item.attrs, "mutate") {
                     let mut ret = vec![item.clone()];
-                    macro_rules! mutate(($mutator:expr, $item:expr) => ({
-                        // Build the mutated function
-                        let mut_fn = mutator::mutate(&mut $mutator, Annotatable::Item($item.clone()));
+                    macro_rules! register(($mut_fn:expr, $item:expr) => ({
+                        let mut_fn = $mut_fn;
                         // Add its rename to the table
                         {
                             // need own scope since we mutably borrow `self`, which
@@ -62,10 +65,71 @@ impl fold::Folder for Locator {
                         // Queue it for attachment to AST
                         ret.push(P(fold::noop_fold_item_simple(mut_fn, self)));
                     }));
+                    macro_rules! mutate(($mutator:expr, $item:expr) => ({
+                        // Build the mutated function
+                        let mut m = $mutator;
+                        let mut_fn = mutator::mutate(&mut m, Annotatable::Item($item.clone()));
+                        // Skip it if it is identical to the original
+                        if m.did_anything() {
+                            register!(mut_fn, $item);
+                        }
+                    }));
+                    macro_rules! mutate_sites(($mutator:ty, $item:expr) => ({
+                        // Build one mutated function per site
+                        for mut_fn in mutator::mutate_sites::<$mutator>(&$item) {
+                            register!(mut_fn, $item);
+                        }
+                    }));
+                    macro_rules! mutate_sites_with(($new:expr, $item:expr) => ({
+                        // As above, but with a constructor that needs more than
+                        // the site index
+                        let mut_fns = mutator::mutate_sites_with(&$item, $new);
+                        for mut_fn in mut_fns {
+                            register!(mut_fn, $item);
+                        }
+                    }));
+
+                    // Mutants are made from a copy with guarded loops, if enabled,
+                    // so that the mutators will go into the loops
+                    let item = if self.config.guard_loops {
+                        mutator::guard_loops(&item)
+                    } else {
+                        item
+                    };
 
                     mutate!(mutator::IfSwap::new(), item);
                     mutate!(mutator::IfTrue::new(), item);
                     mutate!(mutator::IfFalse::new(), item);
+                    mutate_sites!(mutator::ArithSwap, item);
+                    mutate_sites!(mutator::Boundary, item);
+                    mutate_sites!(mutator::LogicSwap, item);
+                    mutate_sites!(mutator::LogicOperand, item);
+                    mutate_sites!(mutator::IntLit, item);
+                    mutate_sites!(mutator::BoolFlip, item);
+                    mutate_sites!(mutator::ReturnValue, item);
+                    mutate_sites!(mutator::StmtDelete, item);
+                    mutate_sites!(mutator::UnaryDelete, item);
+                    mutate_sites!(mutator::NotInsert, item);
+                    mutate_sites!(mutator::ArmSwap, item);
+                    mutate_sites!(mutator::ArmGuard, item);
+                    mutate_sites!(mutator::ArmWildcard, item);
+                    mutate_sites!(mutator::RangeBound, item);
+                    mutate_sites!(mutator::AssignOp, item);
+                    mutate_sites!(mutator::BitSwap, item);
+                    mutate_sites!(mutator::CmpSwap, item);
+                    mutate_sites!(mutator::TryReturn, item);
+                    mutate_sites!(mutator::CtorSwap, item);
+                    mutate_sites!(mutator::TextLit, item);
+                    mutate_sites!(mutator::FloatSwap, item);
+                    mutate_sites!(mutator::CastSwap, item);
+                    mutate_sites!(mutator::AtomicSwap, item);
+                    mutate_sites_with!(|n| mutator::OverflowSwap::new(n, self.config.wrap_plus), item);
+                    mutate_sites_with!(|n| mutator::MethodSwap::new(n, &self.config.swap_methods), item);
+                    mutate_sites_with!(|n| mutator::MethodDelete::new(n, &self.config.identity_methods), item);
+                    mutate_sites!(mutator::VarSwap, item);
+                    mutate_sites!(mutator::LoopZero, item);
+                    mutate_sites!(mutator::LoopOnce, item);
+                    mutate_sites!(mutator::BreakSwap, item);
 
                     // put all the items on the stack...
                     SmallVector::many(ret)
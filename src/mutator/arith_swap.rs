@@ -0,0 +1,104 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// The operators which ArithSwap exchanges for each other
+const ARITH_OPS: [ast::BinOp_; 5] = [
+    ast::BinOp_::BiAdd,
+    ast::BinOp_::BiSub,
+    ast::BinOp_::BiMul,
+    ast::BinOp_::BiDiv,
+    ast::BinOp_::BiRem
+];
+
+/// A Mutator which replaces a single arithmetic operator (`+`, `-`, `*`,
+/// `/` or `%`) with one of the others. Each operator in the function
+/// therefore gives rise to four mutants.
+pub struct ArithSwap {
+    sites: SiteCounter
+}
+
+impl Mutator for ArithSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_arith_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for ArithSwap {
+    fn at_site(n: usize) -> ArithSwap {
+        ArithSwap { sites: SiteCounter::new(n) }
+    }
+}
+
+impl fold::Folder for ArithSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprBinary(op, lhs, rhs) => {
+                    let mut new_op = op.node;
+                    if ARITH_OPS.contains(&op.node) && !util::is_string_operand(&rhs) {
+                        // Each replacement operator is a separate site
+                        for &rep in ARITH_OPS.iter().filter(|&&rep| rep != op.node) {
+                            if self.sites.next() {
+                                new_op = rep;
+                            }
+                        }
+                    }
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprBinary(codemap::respan(op.span, new_op), lhs, rhs),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // The count of `[x; N]` is part of the type, so only fold `x`
+                ast::Expr_::ExprRepeat(elem, count) => {
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprRepeat(self.fold_expr(elem), count),
+                        span: e.span
+                    }
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_ty(&mut self, t: P<ast::Ty>) -> P<ast::Ty> {
+        // do not touch array lengths
+        t
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
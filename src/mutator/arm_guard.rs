@@ -0,0 +1,73 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// A Mutator which replaces a single `match` guard by `true` or `false`
+pub struct ArmGuard {
+    sites: SiteCounter
+}
+
+impl Mutator for ArmGuard {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_armguard_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for ArmGuard {
+    fn at_site(n: usize) -> ArmGuard {
+        ArmGuard { sites: SiteCounter::new(n) }
+    }
+}
+
+impl fold::Folder for ArmGuard {
+    fn fold_arm(&mut self, arm: ast::Arm) -> ast::Arm {
+        let guard = match arm.guard {
+            Some(guard) => {
+                let mut new_guard = guard;
+                for &b in [true, false].iter() {
+                    if self.sites.next() {
+                        new_guard = util::bool_expr(b, new_guard.span);
+                    }
+                }
+                Some(new_guard)
+            }
+            None => None
+        };
+        fold::noop_fold_arm(ast::Arm { guard: guard, ..arm }, self)
+    }
+
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e| fold::noop_fold_expr(e, self))
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
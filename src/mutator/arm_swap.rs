@@ -0,0 +1,93 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// A Mutator which replaces the body of a single `match` arm with the body
+/// of the next arm. Bodies which use variables bound by their own pattern
+/// cannot be moved, so we skip over them when looking for the next arm.
+pub struct ArmSwap {
+    sites: SiteCounter
+}
+
+impl Mutator for ArmSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_armswap_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for ArmSwap {
+    fn at_site(n: usize) -> ArmSwap {
+        ArmSwap { sites: SiteCounter::new(n) }
+    }
+}
+
+impl fold::Folder for ArmSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprMatch(scrutinee, arms, source) => {
+                    let movable: Vec<bool> = arms.iter().map(|arm|
+                        !util::mentions_any(&arm.body, &util::pat_bindings(&arm.pats))
+                    ).collect();
+                    // Find the (index, new body) pair for our site, if it is here
+                    let mut new_body = None;
+                    for i in 0..arms.len() {
+                        let next = (1..arms.len()).map(|d| (i + d) % arms.len())
+                                                  .find(|&j| movable[j]);
+                        if let Some(j) = next {
+                            if self.sites.next() {
+                                new_body = Some((i, arms[j].body.clone()));
+                            }
+                        }
+                    }
+                    let arms = arms.into_iter().enumerate().map(|(i, arm)|
+                        match new_body {
+                            Some((n, ref body)) if n == i => ast::Arm { body: body.clone(), ..arm },
+                            _ => arm
+                        }
+                    ).collect();
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprMatch(scrutinee, arms, source),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,91 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// A Mutator which makes a single `match` behave as though its `_` arm
+/// came first. We cannot literally move the arm, since the compiler will
+/// reject the arms after it as unreachable; instead we copy its body into
+/// every other arm, which has the same effect. This is always possible
+/// since `_` binds no variables.
+pub struct ArmWildcard {
+    sites: SiteCounter
+}
+
+impl Mutator for ArmWildcard {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_armwildcard_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for ArmWildcard {
+    fn at_site(n: usize) -> ArmWildcard {
+        ArmWildcard { sites: SiteCounter::new(n) }
+    }
+}
+
+/// Whether an arm is an unguarded `_ => ...`
+fn is_wildcard(arm: &ast::Arm) -> bool {
+    arm.guard.is_none() && arm.pats.len() == 1 && match arm.pats[0].node {
+        ast::Pat_::PatWild(_) => true,
+        _ => false
+    }
+}
+
+impl fold::Folder for ArmWildcard {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprMatch(scrutinee, arms, source) => {
+                    // There is only something to do if some arm precedes the wildcard
+                    let wild_body = match arms.iter().position(is_wildcard) {
+                        Some(i) if i > 0 && self.sites.next() => Some(arms[i].body.clone()),
+                        _ => None
+                    };
+                    let arms = match wild_body {
+                        Some(body) => arms.into_iter().map(|arm| ast::Arm { body: body.clone(), ..arm }).collect(),
+                        None => arms
+                    };
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprMatch(scrutinee, arms, source),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,106 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// A Mutator which changes a single compound assignment such as `+=`,
+/// either to its opposite (`-=`) or to a plain `=`. Shifts are never made
+/// into `=`, since the shift amount often has a different type from the
+/// value being shifted.
+pub struct AssignOp {
+    sites: SiteCounter
+}
+
+impl Mutator for AssignOp {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_assignop_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for AssignOp {
+    fn at_site(n: usize) -> AssignOp {
+        AssignOp { sites: SiteCounter::new(n) }
+    }
+}
+
+/// Returns the opposite of a compound assignment operator
+fn opposite(op: ast::BinOp_) -> Option<ast::BinOp_> {
+    match op {
+        ast::BinOp_::BiAdd => Some(ast::BinOp_::BiSub),
+        ast::BinOp_::BiSub => Some(ast::BinOp_::BiAdd),
+        ast::BinOp_::BiMul => Some(ast::BinOp_::BiDiv),
+        ast::BinOp_::BiDiv => Some(ast::BinOp_::BiMul),
+        ast::BinOp_::BiRem => Some(ast::BinOp_::BiDiv),
+        ast::BinOp_::BiBitAnd => Some(ast::BinOp_::BiBitOr),
+        ast::BinOp_::BiBitOr => Some(ast::BinOp_::BiBitAnd),
+        ast::BinOp_::BiBitXor => Some(ast::BinOp_::BiBitOr),
+        ast::BinOp_::BiShl => Some(ast::BinOp_::BiShr),
+        ast::BinOp_::BiShr => Some(ast::BinOp_::BiShl),
+        _ => None
+    }
+}
+
+impl fold::Folder for AssignOp {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprAssignOp(op, lhs, rhs) => {
+                    // `s += "foo"` on a String has no opposite, nor can it be
+                    // turned into an `=`
+                    let new_node = if util::is_string_operand(&rhs) {
+                        ast::Expr_::ExprAssignOp(op, lhs, rhs)
+                    } else {
+                        let opp = opposite(op.node);
+                        let is_shift = op.node == ast::BinOp_::BiShl || op.node == ast::BinOp_::BiShr;
+                        if opp.is_some() && self.sites.next() {
+                            ast::Expr_::ExprAssignOp(codemap::respan(op.span, opp.unwrap()), lhs, rhs)
+                        } else if !is_shift && self.sites.next() {
+                            ast::Expr_::ExprAssign(lhs, rhs)
+                        } else {
+                            ast::Expr_::ExprAssignOp(op, lhs, rhs)
+                        }
+                    };
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: new_node,
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
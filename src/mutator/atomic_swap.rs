@@ -0,0 +1,181 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::parse::token::str_to_ident;
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// A Mutator for code using atomics. It swaps a single `fetch_add` for
+/// `fetch_sub` or vice-versa, or weakens a single `Ordering` passed to an
+/// atomic method such as `load` or `fetch_or`: `SeqCst` becomes `AcqRel`
+/// (or `Acquire` for a `load` and `Release` for a `store`, since those
+/// panic on `AcqRel`) and `Relaxed`, and the other orderings become
+/// `Relaxed`. The failure ordering of a `compare_exchange` is only ever
+/// weakened to `Acquire` or `Relaxed`, and its success ordering only when
+/// the failure ordering is `Relaxed`, since anything else may panic.
+/// Orderings passed to functions such as `fence`, which panics on
+/// `Relaxed`, are left alone, as are paths which do not look like
+/// orderings, e.g. `Mode::Release`.
+pub struct AtomicSwap {
+    sites: SiteCounter
+}
+
+impl Mutator for AtomicSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_atomic_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for AtomicSwap {
+    fn at_site(n: usize) -> AtomicSwap {
+        AtomicSwap { sites: SiteCounter::new(n) }
+    }
+}
+
+/// Which arguments of a call to the atomic method `method` with `n`
+/// arguments (counting the receiver) are orderings
+fn ordering_args(method: &str, n: usize) -> Vec<usize> {
+    match (method, n) {
+        ("load", 2) => vec![1],
+        ("store", 3) | ("swap", 3) |
+        ("fetch_add", 3) | ("fetch_sub", 3) | ("fetch_and", 3) |
+        ("fetch_nand", 3) | ("fetch_or", 3) | ("fetch_xor", 3) => vec![2],
+        ("compare_and_swap", 4) => vec![3],
+        ("compare_exchange", 5) | ("compare_exchange_weak", 5) => vec![3, 4],
+        _ => vec![]
+    }
+}
+
+/// If `e` is a path to an ordering, either bare like `SeqCst` or through
+/// `Ordering` like `Ordering::SeqCst`, the ordering's name
+fn ordering_name(e: &ast::Expr) -> Option<String> {
+    if let ast::Expr_::ExprPath(None, ref path) = e.node {
+        let n = path.segments.len();
+        if n == 1 || &*path.segments[n - 2].identifier.name.as_str() == "Ordering" {
+            return Some(path.segments[n - 1].identifier.name.as_str().to_string());
+        }
+    }
+    None
+}
+
+/// The orderings to try in place of `ordering` as the `i`th argument of
+/// `method`, whose arguments are `args`
+fn weaker(ordering: &str, method: &str, i: usize, args: &[P<ast::Expr>]) -> Vec<&'static str> {
+    let compare_exchange = method == "compare_exchange" || method == "compare_exchange_weak";
+    if compare_exchange && i == 4 {
+        // A failure ordering may not be `Release` or `AcqRel`
+        return match ordering {
+            "SeqCst" => vec!["Acquire", "Relaxed"],
+            "Acquire" => vec!["Relaxed"],
+            _ => vec![]
+        };
+    }
+    if compare_exchange && ordering_name(&args[4]).as_ref().map(|s| &s[..]) != Some("Relaxed") {
+        // ...nor stronger than the success ordering, so we only weaken
+        // the latter when the failure ordering is already `Relaxed`
+        return vec![];
+    }
+    match ordering {
+        "SeqCst" => {
+            let middle = match method {
+                "load" => "Acquire",
+                "store" => "Release",
+                _ => "AcqRel"
+            };
+            vec![middle, "Relaxed"]
+        }
+        "AcqRel" | "Acquire" | "Release" => vec!["Relaxed"],
+        _ => vec![]
+    }
+}
+
+impl fold::Folder for AtomicSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprMethodCall(ident, tys, mut args) => {
+                    let mut new_ident = ident.node;
+                    let method = ident.node.name.as_str();
+                    // `x.fetch_add(n, order)` has the receiver as an argument
+                    if args.len() == 3 {
+                        let swapped = match method {
+                            "fetch_add" => Some("fetch_sub"),
+                            "fetch_sub" => Some("fetch_add"),
+                            _ => None
+                        };
+                        if let Some(swapped) = swapped {
+                            if self.sites.next() {
+                                new_ident = str_to_ident(swapped);
+                            }
+                        }
+                    }
+                    // Weaken each ordering argument; we keep the path and
+                    // just replace its last segment
+                    for i in ordering_args(method, args.len()) {
+                        let mut new_ordering = None;
+                        if let Some(ordering) = ordering_name(&args[i]) {
+                            for rep in weaker(&ordering, method, i, &args) {
+                                if self.sites.next() {
+                                    new_ordering = Some(rep);
+                                }
+                            }
+                        }
+                        if let Some(rep) = new_ordering {
+                            let (id, span) = (args[i].id, args[i].span);
+                            let new_node = match args[i].node {
+                                ast::Expr_::ExprPath(None, ref path) => {
+                                    let mut new_path = path.clone();
+                                    new_path.segments.last_mut().unwrap().identifier = str_to_ident(rep);
+                                    ast::Expr_::ExprPath(None, new_path)
+                                },
+                                _ => unreachable!()
+                            };
+                            args[i] = P(ast::Expr {
+                                id: id,
+                                node: new_node,
+                                span: span
+                            });
+                        }
+                    }
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprMethodCall(codemap::respan(ident.span, new_ident), tys, args),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,142 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// The bitwise operators, which BitSwap exchanges for each other
+const BIT_OPS: [ast::BinOp_; 3] = [
+    ast::BinOp_::BiBitAnd,
+    ast::BinOp_::BiBitOr,
+    ast::BinOp_::BiBitXor
+];
+
+/// A Mutator which changes a single bitwise or shift operation: `&`, `|`
+/// and `^` are replaced by each other, `<<` and `>>` are swapped, and the
+/// amount of a shift is moved up or down by one.
+pub struct BitSwap {
+    sites: SiteCounter
+}
+
+impl Mutator for BitSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_bitswap_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for BitSwap {
+    fn at_site(n: usize) -> BitSwap {
+        BitSwap { sites: SiteCounter::new(n) }
+    }
+}
+
+/// Moves a shift amount up or down by one. For literal amounts we skip
+/// any change which would shift by a whole 8, 16, 32 or 64 bits, or by
+/// a negative amount, since the compiler rejects these outright.
+fn nudge(amount: &P<ast::Expr>, up: bool) -> Option<P<ast::Expr>> {
+    if let ast::Expr_::ExprLit(ref lit) = amount.node {
+        if let ast::Lit_::LitInt(n, ty) = lit.node {
+            let new_n = match (up, n) {
+                (true, 7) | (true, 15) | (true, 31) | (true, 63) => return None,
+                (true, n) => n + 1,
+                (false, 0) => return None,
+                (false, n) => n - 1
+            };
+            return Some(util::lit_expr(ast::Lit_::LitInt(new_n, ty), amount.span));
+        }
+    }
+    let op = codemap::respan(amount.span, if up { ast::BinOp_::BiAdd } else { ast::BinOp_::BiSub });
+    Some(util::expr(ast::Expr_::ExprBinary(op, amount.clone(), util::int_expr(1, amount.span)), amount.span))
+}
+
+impl fold::Folder for BitSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprBinary(op, lhs, rhs) => {
+                    let mut new_op = op.node;
+                    let mut new_rhs = None;
+                    match op.node {
+                        ast::BinOp_::BiBitAnd | ast::BinOp_::BiBitOr | ast::BinOp_::BiBitXor => {
+                            for &rep in BIT_OPS.iter().filter(|&&rep| rep != op.node) {
+                                if self.sites.next() {
+                                    new_op = rep;
+                                }
+                            }
+                        },
+                        ast::BinOp_::BiShl | ast::BinOp_::BiShr => {
+                            if self.sites.next() {
+                                new_op = if op.node == ast::BinOp_::BiShl {
+                                    ast::BinOp_::BiShr
+                                } else {
+                                    ast::BinOp_::BiShl
+                                };
+                            }
+                            for &up in [true, false].iter() {
+                                if let Some(amount) = nudge(&rhs, up) {
+                                    if self.sites.next() {
+                                        new_rhs = Some(amount);
+                                    }
+                                }
+                            }
+                        },
+                        _ => {}
+                    }
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprBinary(codemap::respan(op.span, new_op),
+                                                     lhs,
+                                                     new_rhs.unwrap_or(rhs)),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // The count of `[x; N]` is part of the type, so only fold `x`
+                ast::Expr_::ExprRepeat(elem, count) => {
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprRepeat(self.fold_expr(elem), count),
+                        span: e.span
+                    }
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_ty(&mut self, t: P<ast::Ty>) -> P<ast::Ty> {
+        // do not touch array lengths
+        t
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,72 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// A Mutator which flips a single `true` or `false` literal, wherever it
+/// appears: in struct fields, function arguments, return values, etc.
+/// Literal patterns are left alone.
+pub struct BoolFlip {
+    sites: SiteCounter
+}
+
+impl Mutator for BoolFlip {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_boolflip_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for BoolFlip {
+    fn at_site(n: usize) -> BoolFlip {
+        BoolFlip { sites: SiteCounter::new(n) }
+    }
+}
+
+impl fold::Folder for BoolFlip {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        if let ast::Expr_::ExprLit(ref lit) = e.node {
+            if let ast::Lit_::LitBool(b) = lit.node {
+                if self.sites.next() {
+                    return util::bool_expr(!b, e.span);
+                }
+            }
+        }
+        e.map(|e| fold::noop_fold_expr(e, self))
+    }
+
+    fn fold_pat(&mut self, p: P<ast::Pat>) -> P<ast::Pat> {
+        // do not touch literal patterns, which could make a match
+        // non-exhaustive or unreachable
+        p
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,89 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// A Mutator which moves a single relational operator across its
+/// boundary, e.g. replacing `<` by `<=` or `>=` by `>`. Such mutants
+/// survive unless the tests check the edge value itself.
+pub struct Boundary {
+    sites: SiteCounter
+}
+
+impl Mutator for Boundary {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_boundary_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for Boundary {
+    fn at_site(n: usize) -> Boundary {
+        Boundary { sites: SiteCounter::new(n) }
+    }
+}
+
+/// Returns the operator on the other side of the boundary, if any
+fn shift_boundary(op: ast::BinOp_) -> Option<ast::BinOp_> {
+    match op {
+        ast::BinOp_::BiLt => Some(ast::BinOp_::BiLe),
+        ast::BinOp_::BiLe => Some(ast::BinOp_::BiLt),
+        ast::BinOp_::BiGt => Some(ast::BinOp_::BiGe),
+        ast::BinOp_::BiGe => Some(ast::BinOp_::BiGt),
+        _ => None
+    }
+}
+
+impl fold::Folder for Boundary {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprBinary(op, lhs, rhs) => {
+                    let mut new_op = op.node;
+                    if let Some(rep) = shift_boundary(op.node) {
+                        if self.sites.next() {
+                            new_op = rep;
+                        }
+                    }
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprBinary(codemap::respan(op.span, new_op), lhs, rhs),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
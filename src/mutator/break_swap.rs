@@ -0,0 +1,66 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// A Mutator which replaces a single `break` by `continue` or vice-versa,
+/// keeping any label. Since mutators do not recurse into unguarded loops,
+/// this only ever sees the `break`s and `continue`s of guarded ones.
+pub struct BreakSwap {
+    sites: SiteCounter
+}
+
+impl Mutator for BreakSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_breakswap_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for BreakSwap {
+    fn at_site(n: usize) -> BreakSwap {
+        BreakSwap { sites: SiteCounter::new(n) }
+    }
+}
+
+impl fold::Folder for BreakSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e| {
+            let node = match e.node {
+                ast::Expr_::ExprBreak(label) if self.sites.next() => ast::Expr_::ExprAgain(label),
+                ast::Expr_::ExprAgain(label) if self.sites.next() => ast::Expr_::ExprBreak(label),
+                node => node
+            };
+            // ...and continue
+            fold::noop_fold_expr(ast::Expr { node: node, ..e }, self)
+        })
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,145 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// The integer types which may be the target of a cast, with their width
+/// in bits. We take `usize` and `isize` to be 64 bits wide; if they are
+/// narrower, some of their mutants are equivalent to the original.
+const INT_TYPES: [(&'static str, usize); 10] = [
+    ("u8", 8),
+    ("u16", 16),
+    ("u32", 32),
+    ("u64", 64),
+    ("usize", 64),
+    ("i8", 8),
+    ("i16", 16),
+    ("i32", 32),
+    ("i64", 64),
+    ("isize", 64)
+];
+
+/// A Mutator which routes a single integer cast `x as T` through another
+/// integer type, i.e. `(x as U) as T`, so that the result has the same type
+/// but perhaps a different value. `U` is each narrower type of the same
+/// signedness as `T` (so `as u64` is narrowed to `as u32`, `as u16` and
+/// `as u8`) and also the widest narrower type of the opposite signedness
+/// (`as i32`), which sign- rather than zero-extends or vice-versa. Going
+/// through a type of the same width as `T`, or a wider one, is not tried
+/// since casting back to `T` always undoes it.
+pub struct CastSwap {
+    sites: SiteCounter
+}
+
+impl Mutator for CastSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_cast_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for CastSwap {
+    fn at_site(n: usize) -> CastSwap {
+        CastSwap { sites: SiteCounter::new(n) }
+    }
+}
+
+/// If `ty` is an integer type, its name
+pub fn int_type_name(ty: &ast::Ty) -> Option<&'static str> {
+    if let ast::Ty_::TyPath(None, ref path) = ty.node {
+        if !path.global && path.segments.len() == 1 {
+            let name = &*path.segments[0].identifier.name.as_str();
+            return INT_TYPES.iter().find(|&&(int, _)| int == name).map(|&(int, _)| int);
+        }
+    }
+    None
+}
+
+/// The types to cast through in place of casting straight to `target`
+fn replacements(target: &str) -> Vec<&'static str> {
+    let &(_, width) = INT_TYPES.iter().find(|&&(int, _)| int == target).unwrap();
+    let signed = target.starts_with("i");
+    let narrower: Vec<(&'static str, usize)> = INT_TYPES.iter().cloned().filter(|&(_, w)| w < width).collect();
+    let mut ret: Vec<&'static str> = narrower.iter().filter(|&&(int, _)|
+        int.starts_with("i") == signed
+    ).map(|&(int, _)| int).collect();
+    if let Some(widest) = narrower.iter().map(|&(_, w)| w).max() {
+        ret.extend(narrower.iter().filter(|&&(int, w)|
+            w == widest && int.starts_with("i") != signed
+        ).map(|&(int, _)| int));
+    }
+    ret
+}
+
+/// Creates the type with the given (single-segment) name
+fn named_ty(name: &str, span: codemap::Span) -> P<ast::Ty> {
+    P(ast::Ty {
+        id: ast::DUMMY_NODE_ID,
+        node: ast::Ty_::TyPath(None, ast::Path {
+            span: span,
+            global: false,
+            segments: vec![util::str_to_pathseg(name)]
+        }),
+        span: span
+    })
+}
+
+impl fold::Folder for CastSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprCast(expr, ty) => {
+                    let mut new_expr = expr;
+                    if let Some(target) = int_type_name(&ty) {
+                        // Each type to cast through is a separate site
+                        for through in replacements(target) {
+                            if self.sites.next() {
+                                let span = new_expr.span;
+                                let cast = ast::Expr_::ExprCast(new_expr, named_ty(through, ty.span));
+                                new_expr = util::expr(cast, span);
+                            }
+                        }
+                    }
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprCast(new_expr, ty),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
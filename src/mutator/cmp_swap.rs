@@ -0,0 +1,81 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// A Mutator which swaps the operands of a single `<`, `<=`, `>` or `>=`.
+/// This works on every comparison, e.g. `let less = x < y` or a closure
+/// body, not just `if` conditions. We do not exchange `==` and `!=` here:
+/// `a != b` is just `!(a == b)`, which NotInsert already produces for every
+/// equality test that is not a whole `if` condition (and IfSwap for those).
+pub struct CmpSwap {
+    sites: SiteCounter
+}
+
+impl Mutator for CmpSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_cmpswap_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for CmpSwap {
+    fn at_site(n: usize) -> CmpSwap {
+        CmpSwap { sites: SiteCounter::new(n) }
+    }
+}
+
+impl fold::Folder for CmpSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprBinary(op, lhs, rhs) => {
+                    let new_node = match op.node {
+                        ast::BinOp_::BiLt | ast::BinOp_::BiLe |
+                        ast::BinOp_::BiGt | ast::BinOp_::BiGe if self.sites.next() => {
+                            ast::Expr_::ExprBinary(op, rhs, lhs)
+                        },
+                        _ => ast::Expr_::ExprBinary(op, lhs, rhs)
+                    };
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: new_node,
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,219 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+use syntax::util::small_vector::SmallVector;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use mutator::return_value;
+use util;
+
+/// A Mutator which changes a single `Option` or `Result` constructor:
+/// `Some(x)` becomes `None`, `Ok(x)` becomes `Err(e)` and `Err(e)`
+/// becomes `Ok(x)`, where `x` and `e` are plausible values of the `T`
+/// and `E` of the function's declared `Result<T, E>` return type.
+///
+/// We only replace a constructor when it is being returned from the
+/// function, i.e. when it is the argument of a `return` or in tail
+/// position, since only there is its type fixed by the function's
+/// signature; elsewhere, e.g. `let v = None;` may not compile. As with
+/// ReturnValue, only types which we recognize by name are supported: in
+/// particular we never use `Ok(Default::default())`, since the mutant
+/// would not compile if `T` does not implement `Default`.
+pub struct CtorSwap {
+    sites: SiteCounter,
+    /// Plausible values for the `T` of the function's return type
+    oks: Vec<P<ast::Expr>>,
+    /// Plausible values for the `E` of the function's return type
+    errs: Vec<P<ast::Expr>>,
+    /// How many closures we are inside, since `return` in a closure
+    /// does not return from the function
+    closure_depth: usize
+}
+
+impl Mutator for CtorSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_ctorswap_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for CtorSwap {
+    fn at_site(n: usize) -> CtorSwap {
+        CtorSwap {
+            sites: SiteCounter::new(n),
+            oks: vec![],
+            errs: vec![],
+            closure_depth: 0
+        }
+    }
+}
+
+/// If an expression is a call to `Some`, `Ok` or `Err`, returns which
+fn ctor_name(e: &ast::Expr) -> Option<String> {
+    if let ast::Expr_::ExprCall(ref f, ref args) = e.node {
+        if let ast::Expr_::ExprPath(None, ref path) = f.node {
+            if args.len() == 1 && path.segments.len() == 1 {
+                let name = path.segments[0].identifier.name.as_str().to_string();
+                if name == "Some" || name == "Ok" || name == "Err" {
+                    return Some(name);
+                }
+            }
+        }
+    }
+    None
+}
+
+impl CtorSwap {
+    /// Counts the sites for a single expression, returning its replacement
+    /// if one of them is the chosen site
+    fn replacement(&mut self, e: &ast::Expr, returned: bool) -> Option<P<ast::Expr>> {
+        if !returned {
+            return None;
+        }
+        let name = match ctor_name(e) {
+            Some(name) => name,
+            None => return None
+        };
+        let mut ret = None;
+        if name == "Some" {
+            if self.sites.next() {
+                ret = Some(util::path_expr(&["None"], e.span));
+            }
+        } else {
+            let (new_name, values) = if name == "Ok" {
+                ("Err", &self.errs)
+            } else {
+                ("Ok", &self.oks)
+            };
+            for v in values.iter() {
+                if self.sites.next() {
+                    ret = Some(util::call_expr(&[new_name], vec![v.clone()], e.span));
+                }
+            }
+        }
+        ret
+    }
+
+    /// Folds an expression, which is being returned from the function if
+    /// `returned` is set
+    fn fold_ctor(&mut self, e: P<ast::Expr>, returned: bool) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        if let Some(rep) = self.replacement(&e, returned) {
+            return rep;
+        }
+        e.map(|e| {
+            let node = match e.node {
+                ast::Expr_::ExprRet(Some(x)) => {
+                    let top = self.closure_depth == 0;
+                    ast::Expr_::ExprRet(Some(self.fold_ctor(x, top)))
+                },
+                ast::Expr_::ExprClosure(capture, decl, body) => {
+                    self.closure_depth += 1;
+                    let body = self.fold_block(body);
+                    self.closure_depth -= 1;
+                    ast::Expr_::ExprClosure(capture, decl, body)
+                },
+                node => {
+                    if !returned {
+                        return fold::noop_fold_expr(ast::Expr { node: node, ..e }, self);
+                    }
+                    // Pass on "returnedness" to anything in tail position
+                    match node {
+                        ast::Expr_::ExprIf(cond, block, elseexpr) => {
+                            ast::Expr_::ExprIf(self.fold_expr(cond),
+                                               self.fold_tail(block),
+                                               elseexpr.map(|x| self.fold_ctor(x, true)))
+                        },
+                        ast::Expr_::ExprIfLet(pat, expr, block, elseexpr) => {
+                            ast::Expr_::ExprIfLet(self.fold_pat(pat),
+                                                  self.fold_expr(expr),
+                                                  self.fold_tail(block),
+                                                  elseexpr.map(|x| self.fold_ctor(x, true)))
+                        },
+                        ast::Expr_::ExprMatch(expr, arms, source) => {
+                            let expr = self.fold_expr(expr);
+                            let arms = arms.into_iter().map(|arm| ast::Arm {
+                                attrs: arm.attrs,
+                                pats: arm.pats.into_iter().map(|p| self.fold_pat(p)).collect(),
+                                guard: arm.guard.map(|g| self.fold_expr(g)),
+                                body: self.fold_ctor(arm.body, true)
+                            }).collect();
+                            ast::Expr_::ExprMatch(expr, arms, source)
+                        },
+                        ast::Expr_::ExprBlock(block) => {
+                            ast::Expr_::ExprBlock(self.fold_tail(block))
+                        },
+                        ast::Expr_::ExprParen(x) => {
+                            ast::Expr_::ExprParen(self.fold_ctor(x, true))
+                        },
+                        // Everything else just continue folding
+                        node => return fold::noop_fold_expr(ast::Expr { node: node, ..e }, self)
+                    }
+                }
+            };
+            ast::Expr { node: node, ..e }
+        })
+    }
+
+    /// Folds a block whose value is being returned from the function
+    fn fold_tail(&mut self, b: P<ast::Block>) -> P<ast::Block> {
+        b.map(|b| {
+            let stmts = b.stmts.into_iter().flat_map(|s| self.fold_stmt(s).into_iter()).collect();
+            let expr = b.expr.map(|x| self.fold_ctor(x, true));
+            ast::Block { stmts: stmts, expr: expr, ..b }
+        })
+    }
+}
+
+impl fold::Folder for CtorSwap {
+    fn fold_item_simple(&mut self, i: ast::Item) -> ast::Item {
+        let node = match i.node {
+            ast::Item_::ItemFn(decl, unsafety, abi, generics, block) => {
+                // Find values for `T` and `E` if we return a `Result<T, E>`
+                if let Some((ok, err)) = util::result_types(&decl) {
+                    self.oks = return_value::candidates(&ok);
+                    self.errs = return_value::candidates(&err);
+                }
+                let block = self.fold_tail(block);
+                ast::Item_::ItemFn(decl, unsafety, abi, generics, block)
+            },
+            node => node
+        };
+        ast::Item { node: node, ..i }
+    }
+
+    fn fold_item(&mut self, i: P<ast::Item>) -> SmallVector<P<ast::Item>> {
+        // Nested items have their own return types
+        SmallVector::one(i)
+    }
+
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        self.fold_ctor(e, false)
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
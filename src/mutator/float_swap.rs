@@ -0,0 +1,195 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::parse::token::{self, str_to_ident};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// The float rounding methods, which FloatSwap exchanges for each other
+const ROUNDING: [&'static str; 4] = ["floor", "ceil", "round", "trunc"];
+
+/// A Mutator aimed at floating-point code. It replaces a single float
+/// literal `x` by `0.0`, `1.0`, `-x` or `x * 1.5`; swaps one of the
+/// rounding methods `floor`, `ceil`, `round` and `trunc` for another;
+/// or, inside a comparison, replaces one `EPSILON` tolerance by `0.0`
+/// (an exact comparison) or by a million times itself (a loose one).
+pub struct FloatSwap {
+    sites: SiteCounter,
+    /// How many comparisons we are inside of
+    comparisons: usize
+}
+
+impl Mutator for FloatSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_float_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for FloatSwap {
+    fn at_site(n: usize) -> FloatSwap {
+        FloatSwap {
+            sites: SiteCounter::new(n),
+            comparisons: 0
+        }
+    }
+}
+
+/// Whether the operator compares its operands
+fn is_comparison(op: ast::BinOp_) -> bool {
+    match op {
+        ast::BinOp_::BiLt | ast::BinOp_::BiLe |
+        ast::BinOp_::BiGt | ast::BinOp_::BiGe |
+        ast::BinOp_::BiEq | ast::BinOp_::BiNe => true,
+        _ => false
+    }
+}
+
+/// Creates a float literal with the given text and the same suffix as `like`
+fn float_like(s: &str, like: &ast::Lit_, span: codemap::Span) -> P<ast::Expr> {
+    let s = token::intern_and_get_ident(s);
+    match *like {
+        ast::Lit_::LitFloat(_, ty) => util::lit_expr(ast::Lit_::LitFloat(s, ty), span),
+        _ => util::lit_expr(ast::Lit_::LitFloatUnsuffixed(s), span)
+    }
+}
+
+/// Wraps an expression in parentheses
+fn paren(e: P<ast::Expr>) -> P<ast::Expr> {
+    let span = e.span;
+    util::expr(ast::Expr_::ExprParen(e), span)
+}
+
+/// The expressions to try in place of the float literal `e`, one mutant each
+fn literal_replacements(e: &P<ast::Expr>, lit: &ast::Lit_) -> Vec<P<ast::Expr>> {
+    let value = match *lit {
+        ast::Lit_::LitFloat(ref s, _) |
+        ast::Lit_::LitFloatUnsuffixed(ref s) => s.replace("_", "").parse::<f64>().ok(),
+        _ => None
+    };
+    let mut ret = vec![];
+    if let Some(value) = value {
+        if value != 0.0 {
+            ret.push(float_like("0.0", lit, e.span));
+        }
+        if value != 1.0 {
+            ret.push(float_like("1.0", lit, e.span));
+        }
+        if value != 0.0 {
+            let neg = util::expr(ast::Expr_::ExprUnary(ast::UnOp::UnNeg, e.clone()), e.span);
+            ret.push(paren(neg));
+            let scale = codemap::respan(e.span, ast::BinOp_::BiMul);
+            let scaled = ast::Expr_::ExprBinary(scale, e.clone(), float_like("1.5", lit, e.span));
+            ret.push(paren(util::expr(scaled, e.span)));
+        }
+    }
+    ret
+}
+
+/// The expressions to try in place of the `EPSILON` path `e`, one mutant each
+fn epsilon_replacements(e: &P<ast::Expr>) -> Vec<P<ast::Expr>> {
+    let scale = codemap::respan(e.span, ast::BinOp_::BiMul);
+    let loose = ast::Expr_::ExprBinary(scale, e.clone(), util::float_expr("1000000.0", e.span));
+    vec![
+        util::float_expr("0.0", e.span),
+        paren(util::expr(loose, e.span))
+    ]
+}
+
+impl fold::Folder for FloatSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        // Literals and EPSILONs are replaced by whole new expressions
+        let replacements = match e.node {
+            ast::Expr_::ExprLit(ref lit) => literal_replacements(&e, &lit.node),
+            ast::Expr_::ExprPath(None, ref path) if self.comparisons > 0 &&
+                                                    path.segments.last().unwrap().identifier.name.as_str() == "EPSILON" => {
+                epsilon_replacements(&e)
+            },
+            _ => vec![]
+        };
+        if !replacements.is_empty() {
+            let mut new_e = e;
+            for rep in replacements {
+                if self.sites.next() {
+                    new_e = rep;
+                }
+            }
+            return new_e;
+        }
+
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprMethodCall(ident, tys, args) => {
+                    let mut new_ident = ident.node;
+                    let name = ident.node.name.as_str();
+                    if args.len() == 1 && ROUNDING.contains(&name) {
+                        // Each replacement method is a separate site
+                        for &rep in ROUNDING.iter().filter(|&&rep| rep != name) {
+                            if self.sites.next() {
+                                new_ident = str_to_ident(rep);
+                            }
+                        }
+                    }
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprMethodCall(codemap::respan(ident.span, new_ident), tys, args),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                ast::Expr_::ExprBinary(op, lhs, rhs) => {
+                    let comparison = is_comparison(op.node);
+                    if comparison {
+                        self.comparisons += 1;
+                    }
+                    let new_e = fold::noop_fold_expr(ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprBinary(op, lhs, rhs),
+                        span: e.span
+                    }, self);
+                    if comparison {
+                        self.comparisons -= 1;
+                    }
+                    new_e
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_pat(&mut self, p: P<ast::Pat>) -> P<ast::Pat> {
+        // do not touch literal patterns, which could make a match arm
+        // unreachable
+        p
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
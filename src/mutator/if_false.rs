@@ -15,18 +15,24 @@
 use syntax::{ast, fold};
 use syntax::ptr::P;
 
-use mutator::Mutator;
+use mutator::{self, Mutator};
+use util;
 
 /// A Mutator which duplicates the else clauses of `if` statements into their
 /// bodies, effectively making them `if false`. We do it like this
 /// rather than just removing the conditional and promoting the if body
 /// to its parent because the conditional may have side effects.
-pub struct IfFalse;
+///
+/// An `if let` is replaced by its `else` clause, preceded by its expression
+/// for the sake of side effects.
+pub struct IfFalse {
+    did_anything: bool
+}
 
 impl IfFalse {
     /// Create a new IfFalse
     pub fn new() -> IfFalse {
-        IfFalse
+        IfFalse { did_anything: false }
     }
 }
 
@@ -34,13 +40,22 @@ impl Mutator for IfFalse {
     fn rename(&self, name: &str) -> String {
         format!("_mutate_iffalse_{}", name)
     }
+
+    fn did_anything(&self) -> bool {
+        self.did_anything
+    }
 }
 
 impl fold::Folder for IfFalse {
     fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        // At unguarded loops we stop recursing; see `mutator::loop_guard`
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
         e.map(|e|
             match e.node {
                 ast::Expr_::ExprIf(expr, _, elseexpr) => {
+                    self.did_anything = true;
                     // Build the new body
                     let block = ast::Block {
                           stmts: vec![],
@@ -58,14 +73,14 @@ impl fold::Folder for IfFalse {
                     // ...and continue
                     fold::noop_fold_expr(new_if, self)
                 },
-                // At loops we stop recursing because I'm unsure how to guarantee
-                // that I will not cause infinite loops when screwing around in
-                // there
-                ast::Expr_::ExprWhile(_, _, _) |
-                ast::Expr_::ExprWhileLet(_, _, _, _) |
-                ast::Expr_::ExprForLoop(_, _, _, _) |
-                ast::Expr_::ExprLoop(_, _) => {
-                    e
+                ast::Expr_::ExprIfLet(_, expr, _, elseexpr) => {
+                    self.did_anything = true;
+                    // Replace the whole thing by `{ &expr; elseexpr }`. We do not
+                    // copy the else clause into the body, since the pattern's
+                    // bindings could shadow variables that it uses.
+                    util::discard_then(expr, elseexpr).and_then(|new_e|
+                        fold::noop_fold_expr(new_e, self)
+                    )
                 },
                 // Everything else just continue folding
                 _ => fold::noop_fold_expr(e, self)
@@ -15,16 +15,20 @@
 use syntax::{ast, fold};
 use syntax::ptr::P;
 
-use mutator::Mutator;
+use mutator::{self, Mutator};
+use util;
 
 /// A Mutator which negates `if` conditionals, effectively swapping
-/// the if and else clauses
-pub struct IfSwap;
+/// the if and else clauses. For an `if let` we swap the clauses directly,
+/// provided neither of them uses the pattern's bindings.
+pub struct IfSwap {
+    did_anything: bool
+}
 
 impl IfSwap {
     /// Create a new IfSwap
     pub fn new() -> IfSwap {
-        IfSwap
+        IfSwap { did_anything: false }
     }
 }
 
@@ -32,13 +36,22 @@ impl Mutator for IfSwap {
     fn rename(&self, name: &str) -> String {
         format!("_mutate_ifswap_{}", name)
     }
+
+    fn did_anything(&self) -> bool {
+        self.did_anything
+    }
 }
 
 impl fold::Folder for IfSwap {
     fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        // At unguarded loops we stop recursing; see `mutator::loop_guard`
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
         e.map(|e|
             match e.node {
                 ast::Expr_::ExprIf(expr, block, elseexpr) => {
+                    self.did_anything = true;
                     // Flip the if statement
                     let newexpr = ast::Expr {
                         id: expr.id,
@@ -53,14 +66,44 @@ impl fold::Folder for IfSwap {
                     // ...and continue
                     fold::noop_fold_expr(new_e, self)
                 },
-                // At loops we stop recursing because I'm unsure how to guarantee
-                // that I will not cause infinite loops when screwing around in
-                // there
-                ast::Expr_::ExprWhile(_, _, _) |
-                ast::Expr_::ExprWhileLet(_, _, _, _) |
-                ast::Expr_::ExprForLoop(_, _, _, _) |
-                ast::Expr_::ExprLoop(_, _) => {
-                    e
+                ast::Expr_::ExprIfLet(pat, expr, block, elseexpr) => {
+                    // We can swap the branches only if neither uses the pattern's
+                    // bindings: the body because they will no longer be bound, and
+                    // the else clause because they could shadow its variables.
+                    let bindings = util::pat_bindings(&[pat.clone()]);
+                    let else_block = match elseexpr {
+                        Some(ref x) => match x.node {
+                            ast::Expr_::ExprBlock(ref b) => Some(b.clone()),
+                            _ => None
+                        },
+                        None => Some(P(ast::Block {
+                            stmts: vec![],
+                            expr: None,
+                            id: ast::DUMMY_NODE_ID,
+                            rules: ast::BlockCheckMode::DefaultBlock,
+                            span: block.span
+                        }))
+                    };
+                    let new_node = match else_block {
+                        Some(ref else_block) if !util::block_mentions_any(&block, &bindings) &&
+                                                !util::block_mentions_any(else_block, &bindings) => {
+                            self.did_anything = true;
+                            let new_else = ast::Expr {
+                                id: ast::DUMMY_NODE_ID,
+                                node: ast::Expr_::ExprBlock(block),
+                                span: e.span
+                            };
+                            ast::Expr_::ExprIfLet(pat, expr, else_block.clone(), Some(P(new_else)))
+                        },
+                        _ => ast::Expr_::ExprIfLet(pat, expr, block, elseexpr)
+                    };
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: new_node,
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
                 },
                 // Everything else just continue folding
                 _ => fold::noop_fold_expr(e, self)
@@ -15,18 +15,24 @@
 use syntax::{ast, fold};
 use syntax::ptr::P;
 
-use mutator::Mutator;
+use mutator::{self, Mutator};
+use util;
 
 /// A Mutator which duplicates the bodies of `if` statements into their
 /// `else`s, effectively making them `if true`. We do it like this
 /// rather than just removing the conditional and promoting the if body
 /// to its parent because the conditional may have side effects.
-pub struct IfTrue;
+///
+/// An `if let` is replaced by its body, preceded by its expression for the
+/// sake of side effects, unless the body uses the pattern's bindings.
+pub struct IfTrue {
+    did_anything: bool
+}
 
 impl IfTrue {
     /// Create a new IfTrue
     pub fn new() -> IfTrue {
-        IfTrue
+        IfTrue { did_anything: false }
     }
 }
 
@@ -34,13 +40,22 @@ impl Mutator for IfTrue {
     fn rename(&self, name: &str) -> String {
         format!("_mutate_iftrue_{}", name)
     }
+
+    fn did_anything(&self) -> bool {
+        self.did_anything
+    }
 }
 
 impl fold::Folder for IfTrue {
     fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        // At unguarded loops we stop recursing; see `mutator::loop_guard`
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
         e.map(|e|
             match e.node {
                 ast::Expr_::ExprIf(expr, block, _) => {
+                    self.did_anything = true;
                     // Create the new else clause
                     let new_else = ast::Expr {
                         id: ast::DUMMY_NODE_ID,
@@ -56,14 +71,25 @@ impl fold::Folder for IfTrue {
                     // ...and continue
                     fold::noop_fold_expr(new_if, self)
                 },
-                // At loops we stop recursing because I'm unsure how to guarantee
-                // that I will not cause infinite loops when screwing around in
-                // there
-                ast::Expr_::ExprWhile(_, _, _) |
-                ast::Expr_::ExprWhileLet(_, _, _, _) |
-                ast::Expr_::ExprForLoop(_, _, _, _) |
-                ast::Expr_::ExprLoop(_, _) => {
-                    e
+                ast::Expr_::ExprIfLet(pat, expr, block, elseexpr) => {
+                    let bindings = util::pat_bindings(&[pat.clone()]);
+                    if util::block_mentions_any(&block, &bindings) {
+                        // The body needs the pattern's bindings, so we cannot
+                        // take it unconditionally; just continue
+                        let new_e = ast::Expr {
+                            id: e.id,
+                            node: ast::Expr_::ExprIfLet(pat, expr, block, elseexpr),
+                            span: e.span
+                        };
+                        fold::noop_fold_expr(new_e, self)
+                    } else {
+                        self.did_anything = true;
+                        // Replace the whole thing by `{ &expr; { block } }`
+                        let body = util::expr(ast::Expr_::ExprBlock(block), e.span);
+                        util::discard_then(expr, Some(body)).and_then(|new_e|
+                            fold::noop_fold_expr(new_e, self)
+                        )
+                    }
                 },
                 // Everything else just continue folding
                 _ => fold::noop_fold_expr(e, self)
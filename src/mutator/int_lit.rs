@@ -0,0 +1,165 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use std::u64;
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// A Mutator which replaces a single integer literal: `0` becomes `1` and
+/// any other value `n` becomes `n + 1`, `n - 1` and `0`. The literal's
+/// suffix is kept. Literals in types, patterns and array-repeat counts
+/// are left alone since changing them would change the program's types,
+/// as are shift amounts, which BitSwap takes care of.
+pub struct IntLit {
+    sites: SiteCounter
+}
+
+impl Mutator for IntLit {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_intlit_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for IntLit {
+    fn at_site(n: usize) -> IntLit {
+        IntLit { sites: SiteCounter::new(n) }
+    }
+}
+
+/// The largest value a literal with the given suffix can take
+fn max_value(ty: ast::LitIntType) -> u64 {
+    match ty {
+        ast::LitIntType::SignedIntLit(ast::IntTy::TyI8, _) => 0x7f,
+        ast::LitIntType::SignedIntLit(ast::IntTy::TyI16, _) => 0x7fff,
+        ast::LitIntType::SignedIntLit(ast::IntTy::TyI32, _) => 0x7fffffff,
+        ast::LitIntType::SignedIntLit(_, _) => 0x7fffffffffffffff,
+        ast::LitIntType::UnsignedIntLit(ast::UintTy::TyU8) => 0xff,
+        ast::LitIntType::UnsignedIntLit(ast::UintTy::TyU16) => 0xffff,
+        ast::LitIntType::UnsignedIntLit(ast::UintTy::TyU32) => 0xffffffff,
+        ast::LitIntType::UnsignedIntLit(_) |
+        ast::LitIntType::UnsuffixedIntLit(_) => u64::MAX
+    }
+}
+
+/// The values to try in place of the literal `n`, one mutant each
+fn replacements(n: u64, ty: ast::LitIntType) -> Vec<u64> {
+    if n == 0 {
+        return vec![1];
+    }
+    let mut ret = vec![];
+    if n < max_value(ty) {
+        ret.push(n + 1);
+    }
+    // for n == 1 this would duplicate the 0 below
+    if n > 1 {
+        ret.push(n - 1);
+    }
+    ret.push(0);
+    ret
+}
+
+/// Whether `op` is `<<` or `>>`
+fn is_shift(op: ast::BinOp_) -> bool {
+    op == ast::BinOp_::BiShl || op == ast::BinOp_::BiShr
+}
+
+impl fold::Folder for IntLit {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprLit(lit) => {
+                    let int = match lit.node {
+                        ast::Lit_::LitInt(n, ty) => Some((n, ty)),
+                        _ => None
+                    };
+                    let new_lit = match int {
+                        Some((n, ty)) => {
+                            let mut new_n = n;
+                            for rep in replacements(n, ty) {
+                                if self.sites.next() {
+                                    new_n = rep;
+                                }
+                            }
+                            P(codemap::respan(lit.span, ast::Lit_::LitInt(new_n, ty)))
+                        }
+                        None => lit
+                    };
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprLit(new_lit),
+                        span: e.span
+                    }
+                },
+                // Shift amounts are left to BitSwap, which knows which of
+                // them the compiler would reject, so only fold the value
+                ast::Expr_::ExprBinary(op, lhs, rhs) => {
+                    let lhs = self.fold_expr(lhs);
+                    let rhs = if is_shift(op.node) { rhs } else { self.fold_expr(rhs) };
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprBinary(op, lhs, rhs),
+                        span: e.span
+                    }
+                },
+                ast::Expr_::ExprAssignOp(op, lhs, rhs) => {
+                    let lhs = self.fold_expr(lhs);
+                    let rhs = if is_shift(op.node) { rhs } else { self.fold_expr(rhs) };
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprAssignOp(op, lhs, rhs),
+                        span: e.span
+                    }
+                },
+                // The count of `[x; N]` is part of the type, so only fold `x`
+                ast::Expr_::ExprRepeat(elem, count) => {
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprRepeat(self.fold_expr(elem), count),
+                        span: e.span
+                    }
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_ty(&mut self, t: P<ast::Ty>) -> P<ast::Ty> {
+        // do not touch array lengths
+        t
+    }
+
+    fn fold_pat(&mut self, p: P<ast::Pat>) -> P<ast::Pat> {
+        // do not touch literal patterns, which could make a match
+        // non-exhaustive or unreachable
+        p
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,94 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// A Mutator which replaces a single operand of `&&` or `||` by `true`
+/// or `false`. Unlike IfTrue and IfFalse, which treat a compound condition
+/// as one unit, this checks that each sub-condition is tested on its own.
+pub struct LogicOperand {
+    sites: SiteCounter
+}
+
+impl Mutator for LogicOperand {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_logicoperand_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for LogicOperand {
+    fn at_site(n: usize) -> LogicOperand {
+        LogicOperand { sites: SiteCounter::new(n) }
+    }
+}
+
+impl LogicOperand {
+    /// Counts the two sites (`true` and `false`) for a single operand,
+    /// replacing the operand if either is the one we're looking for
+    fn replace_operand(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        for &b in [true, false].iter() {
+            if self.sites.next() {
+                return util::bool_expr(b, e.span);
+            }
+        }
+        e
+    }
+}
+
+impl fold::Folder for LogicOperand {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprBinary(op, lhs, rhs) => {
+                    let (lhs, rhs) = match op.node {
+                        ast::BinOp_::BiAnd | ast::BinOp_::BiOr => {
+                            let lhs = self.replace_operand(lhs);
+                            let rhs = self.replace_operand(rhs);
+                            (lhs, rhs)
+                        }
+                        _ => (lhs, rhs)
+                    };
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprBinary(op, lhs, rhs),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
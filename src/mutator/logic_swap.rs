@@ -0,0 +1,75 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// A Mutator which replaces a single `&&` by `||` or vice-versa
+pub struct LogicSwap {
+    sites: SiteCounter
+}
+
+impl Mutator for LogicSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_logicswap_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for LogicSwap {
+    fn at_site(n: usize) -> LogicSwap {
+        LogicSwap { sites: SiteCounter::new(n) }
+    }
+}
+
+impl fold::Folder for LogicSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprBinary(op, lhs, rhs) => {
+                    let new_op = match op.node {
+                        ast::BinOp_::BiAnd if self.sites.next() => ast::BinOp_::BiOr,
+                        ast::BinOp_::BiOr if self.sites.next() => ast::BinOp_::BiAnd,
+                        other => other
+                    };
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprBinary(codemap::respan(op.span, new_op), lhs, rhs),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,187 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+//! Loop guards, which allow mutators to work inside loops
+//!
+//! Mutating a loop can easily make it run forever, which would hang the
+//! test suite rather than fail it. When `#[mutation_test(guard_loops)]`
+//! is given, we first give every loop in a `#[mutate]` function an
+//! iteration counter which panics when it hits `LOOP_LIMIT`, and mutate
+//! that copy instead of the original. Mutators recurse into guarded loops
+//! but not into unguarded ones.
+//!
+//! The counter is incremented and checked inside an `assert!`, whose
+//! tokens are opaque to the mutators, so they cannot break the guard.
+
+use std::rc::Rc;
+use syntax::{ast, codemap, fold};
+use syntax::parse::token::{self, str_to_ident};
+use syntax::ptr::P;
+
+use aster;
+use mutator;
+use util;
+
+/// The name of the iteration counter we add to each loop
+const COUNTER: &'static str = "_halfsleep_loop_count";
+
+/// The number of iterations after which a guarded loop panics
+const LOOP_LIMIT: &'static str = "1000000";
+
+/// The panic message of a guarded loop which has run too long
+const LOOP_MESSAGE: &'static str = "halfsleep: loop limit";
+
+/// A Folder which adds an iteration guard to every loop it sees
+struct LoopGuard;
+
+/// Return a copy of a function in which every loop is guarded
+pub fn guard_loops(item: &P<ast::Item>) -> P<ast::Item> {
+    P(fold::Folder::fold_item_simple(&mut LoopGuard, (**item).clone()))
+}
+
+/// Whether a loop body starts with our guard
+pub fn is_guarded(b: &ast::Block) -> bool {
+    let e = match b.stmts.first() {
+        Some(s) => match s.node {
+            ast::Stmt_::StmtSemi(ref e, _) => e,
+            _ => return false
+        },
+        None => return false
+    };
+    match e.node {
+        ast::Expr_::ExprMac(ref mac) => {
+            let ast::Mac_::MacInvocTT(_, ref tts, _) = mac.node;
+            match tts.first() {
+                Some(&ast::TokenTree::TtDelimited(_, ref delimed)) => {
+                    match delimed.tts.first() {
+                        Some(&ast::TokenTree::TtToken(_, token::Token::Ident(ref ident, _))) => {
+                            &*ident.name.as_str() == COUNTER
+                        }
+                        _ => false
+                    }
+                }
+                _ => false
+            }
+        }
+        _ => false
+    }
+}
+
+/// Builds the statement `let mut _halfsleep_loop_count = ::std::u64::MIN;`.
+/// We avoid a literal `0` so that IntLit leaves it alone.
+fn counter_decl(span: codemap::Span) -> P<ast::Stmt> {
+    let pat = P(ast::Pat {
+        id: ast::DUMMY_NODE_ID,
+        node: ast::Pat_::PatIdent(ast::BindingMode::BindByValue(ast::Mutability::MutMutable),
+                                  codemap::respan(span, str_to_ident(COUNTER)),
+                                  None),
+        span: span
+    });
+    let init = util::expr(ast::Expr_::ExprPath(None, ast::Path {
+        span: span,
+        global: true,
+        segments: vec![util::str_to_pathseg("std"),
+                       util::str_to_pathseg("u64"),
+                       util::str_to_pathseg("MIN")]
+    }), span);
+    aster::AstBuilder::new().stmt().build_let(pat, None, Some(init))
+}
+
+/// Builds the statement
+/// `assert!({ _halfsleep_loop_count += 1; _halfsleep_loop_count <= LOOP_LIMIT }, LOOP_MESSAGE);`
+fn guard_stmt(span: codemap::Span) -> P<ast::Stmt> {
+    let tt = |tok| ast::TokenTree::TtToken(span, tok);
+    let counter = || token::Token::Ident(str_to_ident(COUNTER), token::IdentStyle::Plain);
+    let check = ast::Delimited {
+        delim: token::DelimToken::Brace,
+        open_span: span,
+        tts: vec![
+            tt(counter()),
+            tt(token::Token::BinOpEq(token::BinOpToken::Plus)),
+            tt(token::Token::Literal(token::Lit::Integer(token::intern("1")), None)),
+            tt(token::Token::Semi),
+            tt(counter()),
+            tt(token::Token::Le),
+            tt(token::Token::Literal(token::Lit::Integer(token::intern(LOOP_LIMIT)), None))
+        ],
+        close_span: span
+    };
+    let tts = vec![
+        ast::TokenTree::TtDelimited(span, Rc::new(check)),
+        tt(token::Token::Comma),
+        tt(token::Token::Literal(token::Lit::Str_(token::intern(LOOP_MESSAGE)), None))
+    ];
+    let path = ast::Path {
+        span: span,
+        global: false,
+        segments: vec![util::str_to_pathseg("assert")]
+    };
+    let mac = codemap::respan(span, ast::Mac_::MacInvocTT(path, tts, ast::EMPTY_CTXT));
+    let assert = util::expr(ast::Expr_::ExprMac(mac), span);
+    P(codemap::respan(span, ast::Stmt_::StmtSemi(assert, ast::DUMMY_NODE_ID)))
+}
+
+/// Adds the guard to the start of a loop body
+fn guard_block(b: P<ast::Block>) -> P<ast::Block> {
+    b.map(|mut b| {
+        b.stmts.insert(0, guard_stmt(b.span));
+        b
+    })
+}
+
+impl fold::Folder for LoopGuard {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        // Guard any inner loops first
+        let e = e.map(|e| fold::noop_fold_expr(e, self));
+        if !mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        let span = e.span;
+        let guarded = e.map(|e| {
+            let node = match e.node {
+                ast::Expr_::ExprWhile(cond, body, label) => {
+                    ast::Expr_::ExprWhile(cond, guard_block(body), label)
+                },
+                ast::Expr_::ExprWhileLet(pat, expr, body, label) => {
+                    ast::Expr_::ExprWhileLet(pat, expr, guard_block(body), label)
+                },
+                ast::Expr_::ExprForLoop(pat, iter, body, label) => {
+                    ast::Expr_::ExprForLoop(pat, iter, guard_block(body), label)
+                },
+                ast::Expr_::ExprLoop(body, label) => {
+                    ast::Expr_::ExprLoop(guard_block(body), label)
+                },
+                _ => unreachable!()
+            };
+            ast::Expr { node: node, ..e }
+        });
+        // Wrap the whole thing in a block which declares the counter
+        let block = ast::Block {
+            stmts: vec![counter_decl(span)],
+            expr: Some(guarded),
+            id: ast::DUMMY_NODE_ID,
+            rules: ast::BlockCheckMode::DefaultBlock,
+            span: span
+        };
+        util::expr(ast::Expr_::ExprBlock(P(block)), span)
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
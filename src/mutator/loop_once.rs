@@ -0,0 +1,93 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use mutator::loop_guard;
+use util;
+
+/// A Mutator which makes a single guarded `while`, `while let` or `for`
+/// loop run at most once, by adding a `break` to the end of its body.
+/// `loop` is left alone since adding a `break` may change its type.
+pub struct LoopOnce {
+    sites: SiteCounter
+}
+
+impl Mutator for LoopOnce {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_looponce_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for LoopOnce {
+    fn at_site(n: usize) -> LoopOnce {
+        LoopOnce { sites: SiteCounter::new(n) }
+    }
+}
+
+/// Adds a `break` to the end of a loop body, if it is the chosen site
+fn break_at_end(sites: &mut SiteCounter, body: P<ast::Block>) -> P<ast::Block> {
+    if !loop_guard::is_guarded(&body) || !sites.next() {
+        return body;
+    }
+    body.map(|mut b| {
+        // Loop bodies have unit type, so we can turn any trailing
+        // expression into a statement
+        if let Some(tail) = b.expr.take() {
+            let span = tail.span;
+            b.stmts.push(P(codemap::respan(span, ast::Stmt_::StmtSemi(tail, ast::DUMMY_NODE_ID))));
+        }
+        let brk = util::expr(ast::Expr_::ExprBreak(None), b.span);
+        b.stmts.push(P(codemap::respan(b.span, ast::Stmt_::StmtSemi(brk, ast::DUMMY_NODE_ID))));
+        b
+    })
+}
+
+impl fold::Folder for LoopOnce {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e| {
+            let node = match e.node {
+                ast::Expr_::ExprWhile(cond, body, label) => {
+                    ast::Expr_::ExprWhile(cond, break_at_end(&mut self.sites, body), label)
+                },
+                ast::Expr_::ExprWhileLet(pat, expr, body, label) => {
+                    ast::Expr_::ExprWhileLet(pat, expr, break_at_end(&mut self.sites, body), label)
+                },
+                ast::Expr_::ExprForLoop(pat, iter, body, label) => {
+                    ast::Expr_::ExprForLoop(pat, iter, break_at_end(&mut self.sites, body), label)
+                },
+                node => node
+            };
+            // ...and continue
+            fold::noop_fold_expr(ast::Expr { node: node, ..e }, self)
+        })
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
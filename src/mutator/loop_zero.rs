@@ -0,0 +1,96 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use mutator::loop_guard;
+use util;
+
+/// A Mutator which makes a single guarded `while`, `while let` or `for`
+/// loop run zero times. The loop's condition (or iterator) is still
+/// evaluated once for the sake of side effects. `loop` is left alone since
+/// its type may depend on it running forever.
+pub struct LoopZero {
+    sites: SiteCounter
+}
+
+impl Mutator for LoopZero {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_loopzero_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for LoopZero {
+    fn at_site(n: usize) -> LoopZero {
+        LoopZero { sites: SiteCounter::new(n) }
+    }
+}
+
+impl fold::Folder for LoopZero {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.and_then(|e|
+            match e.node {
+                ast::Expr_::ExprWhile(cond, body, label) => {
+                    if loop_guard::is_guarded(&body) && self.sites.next() {
+                        util::discard_then(cond, None)
+                    } else {
+                        P(fold::noop_fold_expr(ast::Expr {
+                            node: ast::Expr_::ExprWhile(cond, body, label),
+                            ..e
+                        }, self))
+                    }
+                },
+                ast::Expr_::ExprWhileLet(pat, expr, body, label) => {
+                    if loop_guard::is_guarded(&body) && self.sites.next() {
+                        util::discard_then(expr, None)
+                    } else {
+                        P(fold::noop_fold_expr(ast::Expr {
+                            node: ast::Expr_::ExprWhileLet(pat, expr, body, label),
+                            ..e
+                        }, self))
+                    }
+                },
+                ast::Expr_::ExprForLoop(pat, iter, body, label) => {
+                    if loop_guard::is_guarded(&body) && self.sites.next() {
+                        util::discard_then(iter, None)
+                    } else {
+                        P(fold::noop_fold_expr(ast::Expr {
+                            node: ast::Expr_::ExprForLoop(pat, iter, body, label),
+                            ..e
+                        }, self))
+                    }
+                },
+                // Everything else just continue folding
+                _ => P(fold::noop_fold_expr(e, self))
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
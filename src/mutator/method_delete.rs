@@ -0,0 +1,114 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter};
+
+/// The methods which MethodDelete always removes. `clone` and `cloned`
+/// are deliberately missing: removing them may move out of the receiver
+/// or change an iterator's item type, and the mutant would not compile.
+const DEFAULT_METHODS: [&'static str; 7] = [
+    "rev",
+    "trim",
+    "trim_left",
+    "trim_right",
+    "to_lowercase",
+    "to_uppercase",
+    "abs"
+];
+
+/// A Mutator which removes a single call to an argument-less method that
+/// (usually) gives back something of the same type as its receiver, e.g.
+/// `v.iter().rev().collect()` becomes `v.iter().collect()`. We only do
+/// this when the call is itself the receiver of another method call,
+/// since there autoderef gives the removal the best chance of compiling.
+/// In-place methods like `sort` return `()` and so are never in a chain;
+/// statements like `v.sort();` are left to StmtDelete.
+pub struct MethodDelete {
+    sites: SiteCounter,
+    methods: Vec<String>
+}
+
+impl MethodDelete {
+    /// Create a new MethodDelete which acts on the `n`th site, removing
+    /// the methods in `extra` as well as the default ones
+    pub fn new(n: usize, extra: &[String]) -> MethodDelete {
+        let mut methods: Vec<String> = DEFAULT_METHODS.iter().map(|s| s.to_string()).collect();
+        methods.extend(extra.iter().cloned());
+        MethodDelete {
+            sites: SiteCounter::new(n),
+            methods: methods
+        }
+    }
+
+    /// If `e` is a call to one of our methods with no arguments, its receiver
+    fn removable_receiver(&self, e: &ast::Expr) -> Option<P<ast::Expr>> {
+        if let ast::Expr_::ExprMethodCall(ref ident, _, ref args) = e.node {
+            let name = ident.node.name.as_str();
+            if args.len() == 1 && self.methods.iter().any(|m| &m[..] == name) {
+                return Some(args[0].clone());
+            }
+        }
+        None
+    }
+}
+
+impl Mutator for MethodDelete {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_methoddelete_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl fold::Folder for MethodDelete {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprMethodCall(ident, tys, mut args) => {
+                    // args[0] is the receiver
+                    if let Some(inner) = self.removable_receiver(&args[0]) {
+                        if self.sites.next() {
+                            args[0] = inner;
+                        }
+                    }
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprMethodCall(ident, tys, args),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
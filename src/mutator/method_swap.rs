@@ -0,0 +1,111 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::parse::token::str_to_ident;
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter};
+
+/// The method renames which MethodSwap always does. These are one-way,
+/// since we cannot see types: e.g. slices have both `first` and `last`
+/// but iterators only have `last`, and `Option` has `take` but no
+/// `skip`.
+const DEFAULT_SWAPS: [(&'static str, &'static str); 14] = [
+    ("min", "max"),
+    ("max", "min"),
+    ("first", "last"),
+    ("starts_with", "ends_with"),
+    ("ends_with", "starts_with"),
+    ("any", "all"),
+    ("all", "any"),
+    ("skip", "take"),
+    ("push_front", "push_back"),
+    ("push_back", "push_front"),
+    ("pop_front", "pop_back"),
+    ("pop_back", "pop_front"),
+    ("saturating_add", "saturating_sub"),
+    ("saturating_sub", "saturating_add")
+];
+
+/// A Mutator which renames a single method call to a standard-library
+/// method of the same shape but opposite meaning, e.g. `min` to `max`.
+/// More renames can be given with `#[mutation_test(swap_methods(a = "b"))]`;
+/// each possible rename of each call is a separate site.
+pub struct MethodSwap {
+    sites: SiteCounter,
+    swaps: Vec<(String, String)>
+}
+
+impl MethodSwap {
+    /// Create a new MethodSwap which acts on the `n`th site, doing the
+    /// renames in `extra` as well as the default ones
+    pub fn new(n: usize, extra: &[(String, String)]) -> MethodSwap {
+        let mut swaps: Vec<(String, String)> = DEFAULT_SWAPS.iter().map(|&(from, to)|
+            (from.to_string(), to.to_string())
+        ).collect();
+        swaps.extend(extra.iter().cloned());
+        MethodSwap {
+            sites: SiteCounter::new(n),
+            swaps: swaps
+        }
+    }
+}
+
+impl Mutator for MethodSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_methodswap_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl fold::Folder for MethodSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprMethodCall(ident, tys, args) => {
+                    let mut new_ident = ident.node;
+                    for &(ref from, ref to) in self.swaps.iter() {
+                        if &from[..] == ident.node.name.as_str() && self.sites.next() {
+                            new_ident = str_to_ident(to);
+                        }
+                    }
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprMethodCall(codemap::respan(ident.span, new_ident), tys, args),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
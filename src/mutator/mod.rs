@@ -15,19 +15,140 @@
 use syntax::{ast, fold};
 use syntax::ext::base::Annotatable;
 use syntax::parse::token;
+use syntax::ptr::P;
 
+pub mod arith_swap;
+pub mod arm_guard;
+pub mod arm_swap;
+pub mod arm_wildcard;
+pub mod assign_op;
+pub mod atomic_swap;
+pub mod bit_swap;
+pub mod bool_flip;
+pub mod boundary;
+pub mod break_swap;
+pub mod cast_swap;
+pub mod cmp_swap;
+pub mod ctor_swap;
+pub mod float_swap;
 pub mod if_swap;
 pub mod if_true;
 pub mod if_false;
+pub mod int_lit;
+pub mod logic_operand;
+pub mod logic_swap;
+pub mod loop_guard;
+pub mod loop_once;
+pub mod loop_zero;
+pub mod method_delete;
+pub mod method_swap;
+pub mod not_insert;
+pub mod overflow_swap;
+pub mod range_bound;
+pub mod return_value;
+pub mod stmt_delete;
+pub mod text_lit;
+pub mod try_return;
+pub mod unary_delete;
+pub mod var_swap;
 
+pub use self::arith_swap::ArithSwap;
+pub use self::arm_guard::ArmGuard;
+pub use self::arm_swap::ArmSwap;
+pub use self::arm_wildcard::ArmWildcard;
+pub use self::assign_op::AssignOp;
+pub use self::atomic_swap::AtomicSwap;
+pub use self::bit_swap::BitSwap;
+pub use self::bool_flip::BoolFlip;
+pub use self::boundary::Boundary;
+pub use self::break_swap::BreakSwap;
+pub use self::cast_swap::CastSwap;
+pub use self::cmp_swap::CmpSwap;
+pub use self::ctor_swap::CtorSwap;
+pub use self::float_swap::FloatSwap;
 pub use self::if_swap::IfSwap;
 pub use self::if_true::IfTrue;
 pub use self::if_false::IfFalse;
+pub use self::int_lit::IntLit;
+pub use self::logic_operand::LogicOperand;
+pub use self::logic_swap::LogicSwap;
+pub use self::loop_guard::guard_loops;
+pub use self::loop_once::LoopOnce;
+pub use self::loop_zero::LoopZero;
+pub use self::method_delete::MethodDelete;
+pub use self::method_swap::MethodSwap;
+pub use self::not_insert::NotInsert;
+pub use self::overflow_swap::OverflowSwap;
+pub use self::range_bound::RangeBound;
+pub use self::return_value::ReturnValue;
+pub use self::stmt_delete::StmtDelete;
+pub use self::text_lit::TextLit;
+pub use self::try_return::TryReturn;
+pub use self::unary_delete::UnaryDelete;
+pub use self::var_swap::VarSwap;
 
 /// An object which is able to mutate functions passed into it, e.g.
 /// by replacing all the if statements with their negations
 pub trait Mutator: fold::Folder {
     fn rename(&self, old_name: &str) -> String;
+    /// Whether the mutator changed anything during the last fold. If not,
+    /// the mutated function is identical to the original and is dropped.
+    fn did_anything(&self) -> bool;
+}
+
+/// A Mutator which changes only a single site in a function, selected by
+/// index, so that each mutant it produces corresponds to exactly one
+/// change to the original code
+pub trait SiteMutator: Mutator {
+    /// Create a new mutator which changes only the `n`th site
+    fn at_site(n: usize) -> Self;
+}
+
+/// Counts the mutation sites seen during a fold, so that a `SiteMutator`
+/// can tell when it has reached the one it is supposed to change
+pub struct SiteCounter {
+    target: usize,
+    count: usize
+}
+
+impl SiteCounter {
+    /// Create a new SiteCounter looking for the `target`th site
+    pub fn new(target: usize) -> SiteCounter {
+        SiteCounter {
+            target: target,
+            count: 0
+        }
+    }
+
+    /// The index of the site we are looking for
+    pub fn target(&self) -> usize {
+        self.target
+    }
+
+    /// Record a site, returning whether it is the one to mutate
+    pub fn next(&mut self) -> bool {
+        let ret = self.count == self.target;
+        self.count += 1;
+        ret
+    }
+
+    /// Whether the site to mutate has been seen
+    pub fn did_anything(&self) -> bool {
+        self.count > self.target
+    }
+}
+
+/// Whether an expression is a loop without an iteration guard. Mutators
+/// do not recurse into these since I'm unsure how to guarantee that
+/// changing them will not cause infinite loops.
+pub fn is_unguarded_loop(e: &ast::Expr) -> bool {
+    match e.node {
+        ast::Expr_::ExprWhile(_, ref body, _) |
+        ast::Expr_::ExprWhileLet(_, _, ref body, _) |
+        ast::Expr_::ExprForLoop(_, _, ref body, _) |
+        ast::Expr_::ExprLoop(ref body, _) => !loop_guard::is_guarded(body),
+        _ => false
+    }
 }
 
 /// Use a mutator to produce a function
@@ -47,5 +168,26 @@ pub fn mutate<M: Mutator>(mutator: &mut M, item: Annotatable) -> ast::Item {
     }
 }
 
+/// Use a site mutator to produce one function for each site it finds
+pub fn mutate_sites<M: SiteMutator>(item: &P<ast::Item>) -> Vec<ast::Item> {
+    mutate_sites_with(item, M::at_site)
+}
 
+/// Use a mutator which is created by `new` from a site index to produce
+/// one function for each site it finds
+pub fn mutate_sites_with<M, F>(item: &P<ast::Item>, new: F) -> Vec<ast::Item>
+    where M: Mutator, F: Fn(usize) -> M
+{
+    let mut ret = vec![];
+    for n in 0.. {
+        let mut mutator = new(n);
+        let mut_fn = mutate(&mut mutator, Annotatable::Item(item.clone()));
+        // Once a site index is past the end, all later ones will be too
+        if !mutator.did_anything() {
+            break;
+        }
+        ret.push(mut_fn);
+    }
+    ret
+}
 
@@ -0,0 +1,114 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// Method names which (nearly) always return `bool`, in addition to
+/// anything starting with `is_` or `has_`
+const PREDICATES: [&'static str; 7] = [
+    "contains", "starts_with", "ends_with", "any", "all", "eq", "ne"
+];
+
+/// A Mutator which negates a single boolean expression which is not an
+/// `if` condition (those are handled by IfSwap), e.g. `let ok = a == b;`
+/// or `return x.is_empty();`. Without type information we can only
+/// recognize boolean expressions by their shape: comparisons, `&&`, `||`
+/// and calls to well-known predicate methods.
+pub struct NotInsert {
+    sites: SiteCounter
+}
+
+impl Mutator for NotInsert {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_notinsert_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for NotInsert {
+    fn at_site(n: usize) -> NotInsert {
+        NotInsert { sites: SiteCounter::new(n) }
+    }
+}
+
+/// Whether an expression is certainly boolean, judging by its shape
+fn is_boolean(e: &ast::Expr) -> bool {
+    match e.node {
+        ast::Expr_::ExprBinary(op, _, _) => {
+            match op.node {
+                ast::BinOp_::BiEq | ast::BinOp_::BiNe |
+                ast::BinOp_::BiLt | ast::BinOp_::BiLe |
+                ast::BinOp_::BiGt | ast::BinOp_::BiGe |
+                ast::BinOp_::BiAnd | ast::BinOp_::BiOr => true,
+                _ => false
+            }
+        },
+        ast::Expr_::ExprMethodCall(ref ident, _, _) => {
+            let name = ident.node.name.as_str();
+            name.starts_with("is_") || name.starts_with("has_") ||
+                PREDICATES.iter().any(|&p| p == &*name)
+        },
+        _ => false
+    }
+}
+
+impl fold::Folder for NotInsert {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        let negate = is_boolean(&e) && self.sites.next();
+        e.map(|e| {
+            let span = e.span;
+            let new_e = match e.node {
+                // Skip the condition itself, but not its subexpressions
+                ast::Expr_::ExprIf(cond, block, elseexpr) => {
+                    let cond = cond.map(|c| fold::noop_fold_expr(c, self));
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprIf(cond,
+                                                 self.fold_block(block),
+                                                 elseexpr.map(|x| self.fold_expr(x))),
+                        span: span
+                    }
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            };
+            if negate {
+                ast::Expr {
+                    id: ast::DUMMY_NODE_ID,
+                    node: ast::Expr_::ExprUnary(ast::UnNot, P(new_e)),
+                    span: span
+                }
+            } else {
+                new_e
+            }
+        })
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,209 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::parse::token::str_to_ident;
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter};
+use mutator::cast_swap;
+use util;
+
+/// The overflow behaviours, as prefixes of integer method names
+const KINDS: [&'static str; 3] = ["wrapping", "saturating", "checked"];
+
+/// The operations which have a method for each overflow behaviour
+const OPS: [&'static str; 4] = ["add", "sub", "mul", "shl"];
+
+/// A Mutator which changes the overflow behaviour of a single integer
+/// operation: one of `x.wrapping_add(y)`, `x.saturating_add(y)` and
+/// `x.checked_add(y).unwrap()` becomes each of the others, and likewise for
+/// `sub`, `mul` and `shl` (which has no saturating form). A bare
+/// `checked_add` is left alone since its `Option` may be handled properly.
+///
+/// Also `x + y` becomes `x.wrapping_add(y)`. Without
+/// `#[mutation_test(wrap_plus)]` this is only done when one side's integer
+/// type is written out, as a cast or a suffixed literal, e.g. `n + 1u32`,
+/// since otherwise we cannot tell integers from floats. With `wrap_plus`
+/// every `+` is rewritten, except where one side is plainly a float or a
+/// string.
+pub struct OverflowSwap {
+    sites: SiteCounter,
+    wrap_plus: bool
+}
+
+impl OverflowSwap {
+    /// Create a new OverflowSwap which acts on the `n`th site
+    pub fn new(n: usize, wrap_plus: bool) -> OverflowSwap {
+        OverflowSwap {
+            sites: SiteCounter::new(n),
+            wrap_plus: wrap_plus
+        }
+    }
+}
+
+impl Mutator for OverflowSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_overflow_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+/// Splits a method name like `wrapping_add` into its behaviour and operation
+fn split_name(name: &str) -> Option<(&'static str, &'static str)> {
+    let mut parts = name.splitn(2, '_');
+    let kind = parts.next().and_then(|kind| KINDS.iter().find(|&&k| k == kind));
+    let op = parts.next().and_then(|op| OPS.iter().find(|&&o| o == op));
+    match (kind, op) {
+        (Some(&kind), Some(&op)) => Some((kind, op)),
+        _ => None
+    }
+}
+
+/// Builds `args[0].kind_op(args[1..])`, with an `.unwrap()` for `checked`
+fn overflow_call(kind: &str, op: &str, tys: Vec<P<ast::Ty>>,
+                 args: Vec<P<ast::Expr>>, span: codemap::Span) -> P<ast::Expr> {
+    let method = codemap::respan(span, str_to_ident(&format!("{}_{}", kind, op)));
+    let call = util::expr(ast::Expr_::ExprMethodCall(method, tys, args), span);
+    if kind == "checked" {
+        let unwrap = codemap::respan(span, str_to_ident("unwrap"));
+        util::expr(ast::Expr_::ExprMethodCall(unwrap, vec![], vec![call]), span)
+    } else {
+        call
+    }
+}
+
+/// Whether an expression's integer type is written out: it is a cast to an
+/// integer type or a suffixed integer literal
+fn is_typed_int(e: &ast::Expr) -> bool {
+    match e.node {
+        ast::Expr_::ExprCast(_, ref ty) => cast_swap::int_type_name(ty).is_some(),
+        ast::Expr_::ExprLit(ref lit) => match lit.node {
+            ast::Lit_::LitInt(_, ast::LitIntType::SignedIntLit(_, _)) |
+            ast::Lit_::LitInt(_, ast::LitIntType::UnsignedIntLit(_)) => true,
+            _ => false
+        },
+        ast::Expr_::ExprParen(ref e) => is_typed_int(e),
+        _ => false
+    }
+}
+
+/// Whether a `+` with this left-hand side may not be an integer addition,
+/// or is a literal whose type we would need to know to call a method on it
+fn is_unsure_lhs(e: &ast::Expr) -> bool {
+    match e.node {
+        ast::Expr_::ExprLit(_) => !is_typed_int(e),
+        ast::Expr_::ExprParen(ref e) => is_unsure_lhs(e),
+        _ => util::is_string_operand(e)
+    }
+}
+
+/// Whether `e` is a float literal
+fn is_float_lit(e: &ast::Expr) -> bool {
+    match e.node {
+        ast::Expr_::ExprLit(ref lit) => match lit.node {
+            ast::Lit_::LitFloat(_, _) | ast::Lit_::LitFloatUnsuffixed(_) => true,
+            _ => false
+        },
+        _ => false
+    }
+}
+
+impl OverflowSwap {
+    /// The expressions to try in place of `e`, one mutant each
+    fn replacements(&self, e: &ast::Expr) -> Vec<P<ast::Expr>> {
+        let mut ret = vec![];
+        match e.node {
+            // `x.checked_op(y).unwrap()`
+            ast::Expr_::ExprMethodCall(ref ident, _, ref args) if args.len() == 1 &&
+                                                             &*ident.node.name.as_str() == "unwrap" => {
+                if let ast::Expr_::ExprMethodCall(ref inner, ref tys, ref inner_args) = args[0].node {
+                    if let Some(("checked", op)) = split_name(&*inner.node.name.as_str()) {
+                        for &kind in KINDS.iter().filter(|&&k| k != "checked") {
+                            if kind != "saturating" || op != "shl" {
+                                ret.push(overflow_call(kind, op, tys.clone(), inner_args.clone(), e.span));
+                            }
+                        }
+                    }
+                }
+            },
+            // `x.wrapping_op(y)` and `x.saturating_op(y)`
+            ast::Expr_::ExprMethodCall(ref ident, ref tys, ref args) if args.len() == 2 => {
+                if let Some((old_kind, op)) = split_name(&*ident.node.name.as_str()) {
+                    if old_kind != "checked" {
+                        for &kind in KINDS.iter().filter(|&&k| k != old_kind) {
+                            if kind != "saturating" || op != "shl" {
+                                ret.push(overflow_call(kind, op, tys.clone(), args.clone(), e.span));
+                            }
+                        }
+                    }
+                }
+            },
+            // `x + y`
+            ast::Expr_::ExprBinary(op, ref lhs, ref rhs) if op.node == ast::BinOp_::BiAdd => {
+                let sure = self.wrap_plus || is_typed_int(lhs) || is_typed_int(rhs);
+                if sure && !is_unsure_lhs(lhs) && !is_float_lit(rhs) && !util::is_string_operand(rhs) {
+                    ret.push(overflow_call("wrapping", "add", vec![], vec![lhs.clone(), rhs.clone()], e.span));
+                }
+            },
+            _ => {}
+        }
+        ret
+    }
+}
+
+impl fold::Folder for OverflowSwap {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        // Each replacement is a separate site; once we have made one there
+        // is no need to look inside it
+        for rep in self.replacements(&e) {
+            if self.sites.next() {
+                return rep;
+            }
+        }
+        e.map(|e|
+            match e.node {
+                // The count of `[x; N]` is part of the type, so only fold `x`
+                ast::Expr_::ExprRepeat(elem, count) => {
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprRepeat(self.fold_expr(elem), count),
+                        span: e.span
+                    }
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_ty(&mut self, t: P<ast::Ty>) -> P<ast::Ty> {
+        // do not touch array lengths
+        t
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,96 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// A Mutator which moves a single bound of a range expression up by one,
+/// turning `a..b` into `a..b + 1` or `a + 1..b`. Open ends are left open.
+///
+/// There is no inclusive range expression syntax (`a...b` is only allowed
+/// in patterns), so we cannot swap between inclusive and exclusive ranges;
+/// moving the end bound has the same effect as making `a..b` inclusive.
+pub struct RangeBound {
+    sites: SiteCounter
+}
+
+impl Mutator for RangeBound {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_range_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for RangeBound {
+    fn at_site(n: usize) -> RangeBound {
+        RangeBound { sites: SiteCounter::new(n) }
+    }
+}
+
+impl RangeBound {
+    /// Replaces `bound` by `bound + 1` if this is the chosen site
+    fn bump(&mut self, bound: Option<P<ast::Expr>>) -> Option<P<ast::Expr>> {
+        match bound {
+            Some(b) => {
+                if self.sites.next() {
+                    let span = b.span;
+                    let add = codemap::respan(span, ast::BinOp_::BiAdd);
+                    Some(util::expr(ast::Expr_::ExprBinary(add, b, util::int_expr(1, span)), span))
+                } else {
+                    Some(b)
+                }
+            }
+            None => None
+        }
+    }
+}
+
+impl fold::Folder for RangeBound {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprRange(start, end) => {
+                    let end = self.bump(end);
+                    let start = self.bump(start);
+                    let new_e = ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprRange(start, end),
+                        span: e.span
+                    };
+                    // ...and continue
+                    fold::noop_fold_expr(new_e, self)
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
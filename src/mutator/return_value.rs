@@ -0,0 +1,147 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::ptr::P;
+
+use mutator::{Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// A Mutator which replaces the entire body of a function with a constant
+/// chosen from its declared return type, e.g. `0` or `1` for integers,
+/// `None` for options or `Vec::new()` for vectors. Each candidate value
+/// is a separate mutant; if one survives, no test looks at the result.
+///
+/// Since we have no type information beyond the signature, we only
+/// produce values for types we recognize by name. Anything else (user
+/// types, generics, `Self`) is left alone: we never fall back to
+/// `Default::default()` or `Ok(Default::default())`, since if there is no
+/// `Default` impl the mutant does not compile, which breaks the whole test
+/// crate rather than just failing one test.
+pub struct ReturnValue {
+    sites: SiteCounter
+}
+
+impl Mutator for ReturnValue {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_retval_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for ReturnValue {
+    fn at_site(n: usize) -> ReturnValue {
+        ReturnValue { sites: SiteCounter::new(n) }
+    }
+}
+
+/// The unit value `()`
+fn unit(span: codemap::Span) -> P<ast::Expr> {
+    util::expr(ast::Expr_::ExprTup(vec![]), span)
+}
+
+/// Builds the list of plausible values of the given type, which is empty
+/// if we don't recognize the type
+pub fn candidates(ty: &ast::Ty) -> Vec<P<ast::Expr>> {
+    let sp = ty.span;
+    match ty.node {
+        ast::Ty_::TyTup(ref tys) if tys.is_empty() => vec![unit(sp)],
+        ast::Ty_::TyParen(ref ty) => candidates(ty),
+        // `&str` and `&[T]`
+        ast::Ty_::TyRptr(_, ref mt) if mt.mutbl == ast::Mutability::MutImmutable => {
+            match mt.ty.node {
+                ast::Ty_::TyVec(_) => {
+                    let empty = util::expr(ast::Expr_::ExprVec(vec![]), sp);
+                    vec![util::expr(ast::Expr_::ExprAddrOf(ast::Mutability::MutImmutable, empty), sp)]
+                }
+                ast::Ty_::TyPath(None, ref path) if path.segments.len() == 1 &&
+                                                    &*path.segments[0].identifier.name.as_str() == "str" => {
+                    vec![util::str_expr("", sp)]
+                }
+                _ => vec![]
+            }
+        },
+        ast::Ty_::TyPath(None, ref path) => {
+            let seg = path.segments.last().unwrap();
+            let params: Vec<&P<ast::Ty>> = match seg.parameters {
+                ast::PathParameters::AngleBracketedParameters(ref data) => data.types.iter().collect(),
+                _ => vec![]
+            };
+            match &*seg.identifier.name.as_str() {
+                "bool" => vec![util::bool_expr(false, sp), util::bool_expr(true, sp)],
+                "u8" | "u16" | "u32" | "u64" | "usize" |
+                "i8" | "i16" | "i32" | "i64" | "isize" => {
+                    vec![util::int_expr(0, sp), util::int_expr(1, sp)]
+                }
+                "f32" | "f64" => vec![util::float_expr("0.0", sp), util::float_expr("1.0", sp)],
+                "String" => vec![util::call_expr(&["String", "new"], vec![], sp)],
+                "Vec" => vec![util::call_expr(&["Vec", "new"], vec![], sp)],
+                "HashMap" | "HashSet" | "BTreeMap" | "BTreeSet" | "VecDeque" | "BinaryHeap" => {
+                    vec![util::call_expr(&["Default", "default"], vec![], sp)]
+                }
+                "Option" => {
+                    let mut ret = vec![util::path_expr(&["None"], sp)];
+                    if params.len() == 1 {
+                        ret.extend(candidates(params[0]).into_iter()
+                                                        .map(|v| util::call_expr(&["Some"], vec![v], sp)));
+                    }
+                    ret
+                }
+                "Result" if params.len() == 2 => {
+                    candidates(params[0]).into_iter()
+                                         .map(|v| util::call_expr(&["Ok"], vec![v], sp))
+                                         .collect()
+                }
+                _ => vec![]
+            }
+        },
+        _ => vec![]
+    }
+}
+
+impl fold::Folder for ReturnValue {
+    fn fold_item_simple(&mut self, i: ast::Item) -> ast::Item {
+        // We only look at the signature of the top-level function; there
+        // is no need to recurse since we are replacing the whole body.
+        let node = match i.node {
+            ast::Item_::ItemFn(decl, unsafety, abi, generics, block) => {
+                let values = match decl.output {
+                    ast::FunctionRetTy::Return(ref ty) => candidates(ty),
+                    ast::FunctionRetTy::DefaultReturn(sp) => vec![unit(sp)],
+                    ast::FunctionRetTy::NoReturn(_) => vec![]
+                };
+                let mut new_block = block;
+                for value in values {
+                    if self.sites.next() {
+                        new_block = util::expr_block(value);
+                    }
+                }
+                ast::Item_::ItemFn(decl, unsafety, abi, generics, new_block)
+            },
+            node => node
+        };
+        ast::Item { node: node, ..i }
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,111 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use std::collections::HashSet;
+use syntax::{ast, fold};
+use syntax::ptr::P;
+use syntax::util::small_vector::SmallVector;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// A Mutator which deletes a single statement whose only purpose is its
+/// side effects: a call, method call or (compound) assignment followed by
+/// a semicolon. `let`s, `return`s and macro invocations are never
+/// deleted, since the function may not compile without them.
+pub struct StmtDelete {
+    sites: SiteCounter,
+    /// Variables declared by `let` without an initializer; assignments to
+    /// these are needed for the function to compile
+    uninit: HashSet<ast::Name>
+}
+
+impl Mutator for StmtDelete {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_stmtdelete_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for StmtDelete {
+    fn at_site(n: usize) -> StmtDelete {
+        StmtDelete {
+            sites: SiteCounter::new(n),
+            uninit: HashSet::new()
+        }
+    }
+}
+
+impl StmtDelete {
+    /// Whether the expression of an `expr;` statement can be deleted
+    fn is_deletable(&self, e: &ast::Expr) -> bool {
+        match e.node {
+            ast::Expr_::ExprCall(_, _) |
+            ast::Expr_::ExprMethodCall(_, _, _) |
+            ast::Expr_::ExprAssignOp(_, _, _) => true,
+            ast::Expr_::ExprAssign(ref lhs, _) => {
+                match lhs.node {
+                    ast::Expr_::ExprPath(None, ref path) if path.segments.len() == 1 => {
+                        !self.uninit.contains(&path.segments[0].identifier.name)
+                    }
+                    _ => true
+                }
+            }
+            _ => false
+        }
+    }
+}
+
+impl fold::Folder for StmtDelete {
+    fn fold_stmt(&mut self, s: P<ast::Stmt>) -> SmallVector<P<ast::Stmt>> {
+        let delete = match s.node {
+            ast::Stmt_::StmtSemi(ref e, _) => self.is_deletable(e) && self.sites.next(),
+            ast::Stmt_::StmtDecl(ref decl, _) => {
+                // Statements are folded in order, so we will see any `let`
+                // before the assignments to the variable it declares
+                if let ast::Decl_::DeclLocal(ref local) = decl.node {
+                    if local.init.is_none() {
+                        if let ast::Pat_::PatIdent(_, ref ident, _) = local.pat.node {
+                            self.uninit.insert(ident.node.name);
+                        }
+                    }
+                }
+                false
+            }
+            _ => false
+        };
+        if delete {
+            SmallVector::zero()
+        } else {
+            s.and_then(|s| fold::noop_fold_stmt(s, self))
+        }
+    }
+
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e| fold::noop_fold_expr(e, self))
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,124 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use std::char;
+use syntax::{ast, codemap, fold};
+use syntax::parse::token;
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// The string which TextLit puts in place of nonempty strings
+const MARKER: &'static str = "halfsleep";
+
+/// A Mutator which replaces a single string, character or byte literal:
+/// a string becomes `""` and `"halfsleep"`, and a character or byte
+/// becomes its neighbours. Literals in patterns are left alone, since
+/// changing them could make a match arm unreachable, as are literals
+/// inside macros, which includes all format strings.
+pub struct TextLit {
+    sites: SiteCounter
+}
+
+impl Mutator for TextLit {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_textlit_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for TextLit {
+    fn at_site(n: usize) -> TextLit {
+        TextLit { sites: SiteCounter::new(n) }
+    }
+}
+
+/// The literals to try in place of `lit`, one mutant each
+fn replacements(lit: &ast::Lit_) -> Vec<ast::Lit_> {
+    let mut ret = vec![];
+    match *lit {
+        ast::Lit_::LitStr(ref s, style) => {
+            for rep in ["", MARKER].iter() {
+                if &**s != *rep {
+                    ret.push(ast::Lit_::LitStr(token::intern_and_get_ident(rep), style));
+                }
+            }
+        },
+        ast::Lit_::LitChar(c) => {
+            let c = c as u32;
+            if let Some(rep) = char::from_u32(c + 1) {
+                ret.push(ast::Lit_::LitChar(rep));
+            }
+            if c > 0 {
+                if let Some(rep) = char::from_u32(c - 1) {
+                    ret.push(ast::Lit_::LitChar(rep));
+                }
+            }
+        },
+        ast::Lit_::LitByte(b) => {
+            if b < 0xff {
+                ret.push(ast::Lit_::LitByte(b + 1));
+            }
+            if b > 0 {
+                ret.push(ast::Lit_::LitByte(b - 1));
+            }
+        },
+        _ => {}
+    }
+    ret
+}
+
+impl fold::Folder for TextLit {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprLit(lit) => {
+                    let mut new_lit = lit.node.clone();
+                    for rep in replacements(&lit.node) {
+                        if self.sites.next() {
+                            new_lit = rep;
+                        }
+                    }
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprLit(P(codemap::respan(lit.span, new_lit))),
+                        span: e.span
+                    }
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_pat(&mut self, p: P<ast::Pat>) -> P<ast::Pat> {
+        // do not touch literal patterns, which could make a match arm
+        // unreachable
+        p
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,172 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, codemap, fold};
+use syntax::parse::token::str_to_ident;
+use syntax::ptr::P;
+use syntax::util::small_vector::SmallVector;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use mutator::return_value;
+use util;
+
+/// A Mutator which changes a single `try!(expr)`, checking that the tests
+/// exercise each error path. It is replaced by an early `return Err(e)`,
+/// where `e` is a plausible value of the error type in the function's
+/// declared `Result<T, E>` return type, and its error is also swallowed:
+/// `try!(expr);` becomes `{ &expr; }`, and `let x: T = try!(expr);`
+/// becomes `let x: T = expr.unwrap_or(v);` for plausible values `v` of `T`.
+///
+/// As with ReturnValue, we only know how to build values of types we
+/// recognize by name, so for user-defined error types (or `Result` aliases)
+/// only the first kind of swallowing is done. `try!`s inside closures and
+/// nested functions are left alone since they return from those instead.
+pub struct TryReturn {
+    sites: SiteCounter,
+    /// The plausible error values for the function being mutated
+    errors: Vec<P<ast::Expr>>
+}
+
+impl Mutator for TryReturn {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_tryreturn_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for TryReturn {
+    fn at_site(n: usize) -> TryReturn {
+        TryReturn {
+            sites: SiteCounter::new(n),
+            errors: vec![]
+        }
+    }
+}
+
+/// Whether a macro invocation is a `try!`
+fn is_try(mac: &ast::Mac) -> bool {
+    let ast::Mac_::MacInvocTT(ref path, _, _) = mac.node;
+    path.segments.len() == 1 && &*path.segments[0].identifier.name.as_str() == "try"
+}
+
+impl TryReturn {
+    /// Counts the sites for one `try!`, returning its replacement if one
+    /// of them is the chosen site. If the `try!`'s value is unused,
+    /// `discarded` is set; otherwise `values` are plausible values of it.
+    fn replacement(&mut self, mac: &ast::Mac, span: codemap::Span,
+                   values: Vec<P<ast::Expr>>, discarded: bool) -> Option<P<ast::Expr>> {
+        let mut ret = None;
+        for err in self.errors.iter() {
+            if self.sites.next() {
+                let err = util::call_expr(&["Err"], vec![err.clone()], span);
+                ret = Some(util::expr(ast::Expr_::ExprRet(Some(err)), span));
+            }
+        }
+        // Swallow the error
+        if discarded && self.sites.next() {
+            ret = Some(util::discard_then(util::mac_expr(mac), None));
+        }
+        for v in values {
+            if self.sites.next() {
+                let unwrap_or = codemap::respan(span, str_to_ident("unwrap_or"));
+                let call = ast::Expr_::ExprMethodCall(unwrap_or, vec![], vec![util::mac_expr(mac), v]);
+                ret = Some(util::expr(call, span));
+            }
+        }
+        ret
+    }
+}
+
+impl fold::Folder for TryReturn {
+    fn fold_item_simple(&mut self, i: ast::Item) -> ast::Item {
+        if let ast::Item_::ItemFn(ref decl, _, _, _, _) = i.node {
+            self.errors = match util::result_types(decl) {
+                Some((_, err)) => return_value::candidates(&err),
+                None => vec![]
+            };
+        }
+        fold::noop_fold_item_simple(i, self)
+    }
+
+    fn fold_item(&mut self, i: P<ast::Item>) -> SmallVector<P<ast::Item>> {
+        // Nested items have their own return types
+        SmallVector::one(i)
+    }
+
+    fn fold_stmt(&mut self, s: P<ast::Stmt>) -> SmallVector<P<ast::Stmt>> {
+        // `try!(expr);` in statement position is parsed as a statement
+        // rather than an expression
+        let rep = match s.node {
+            ast::Stmt_::StmtMac(ref mac, _) if is_try(mac) => self.replacement(mac, s.span, vec![], true),
+            _ => None
+        };
+        match rep {
+            Some(rep) => {
+                SmallVector::one(P(codemap::respan(s.span, ast::Stmt_::StmtSemi(rep, ast::DUMMY_NODE_ID))))
+            }
+            None => s.and_then(|s| fold::noop_fold_stmt(s, self))
+        }
+    }
+
+    fn fold_local(&mut self, l: P<ast::Local>) -> P<ast::Local> {
+        // In `let x: T = try!(expr)` we know the type of the `try!`
+        let values = match (&l.ty, &l.init) {
+            (&Some(ref ty), &Some(ref init)) => match init.node {
+                ast::Expr_::ExprMac(ref mac) if is_try(mac) => Some(return_value::candidates(ty)),
+                _ => None
+            },
+            _ => None
+        };
+        match values {
+            Some(values) => l.map(|l| {
+                let init = l.init.unwrap();
+                let rep = match init.node {
+                    ast::Expr_::ExprMac(ref mac) => self.replacement(mac, init.span, values, false),
+                    _ => unreachable!()
+                };
+                ast::Local { init: Some(rep.unwrap_or(init)), ..l }
+            }),
+            None => fold::noop_fold_local(l, self)
+        }
+    }
+
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        // `try!` in a closure returns from the closure
+        if let ast::Expr_::ExprClosure(_, _, _) = e.node {
+            return e;
+        }
+        let rep = match e.node {
+            ast::Expr_::ExprMac(ref mac) if is_try(mac) => self.replacement(mac, e.span, vec![], false),
+            _ => None
+        };
+        match rep {
+            Some(rep) => rep,
+            None => e.map(|e| fold::noop_fold_expr(e, self))
+        }
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,78 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::ptr::P;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+
+/// A Mutator which removes a single unary `!` or `-` from an expression
+pub struct UnaryDelete {
+    sites: SiteCounter
+}
+
+impl Mutator for UnaryDelete {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_unarydelete_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for UnaryDelete {
+    fn at_site(n: usize) -> UnaryDelete {
+        UnaryDelete { sites: SiteCounter::new(n) }
+    }
+}
+
+impl fold::Folder for UnaryDelete {
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        let delete = match e.node {
+            ast::Expr_::ExprUnary(ast::UnNot, _) |
+            ast::Expr_::ExprUnary(ast::UnNeg, _) => self.sites.next(),
+            _ => false
+        };
+        if delete {
+            // Replace the expression by its operand, and continue
+            // folding that
+            e.and_then(|e|
+                match e.node {
+                    ast::Expr_::ExprUnary(_, inner) => self.fold_expr(inner),
+                    _ => unreachable!()
+                }
+            )
+        } else {
+            e.map(|e| fold::noop_fold_expr(e, self))
+        }
+    }
+
+    fn fold_pat(&mut self, p: P<ast::Pat>) -> P<ast::Pat> {
+        // do not touch negative literal patterns, which could make a
+        // match arm unreachable
+        p
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -0,0 +1,256 @@
+// Half Sleep -- Mutation Testing for Rust
+// Written in 2015 by
+//   Andrew Poelstra <apoelstra@wpsoftware.net>
+//
+// To the extent possible under law, the author(s) have dedicated all
+// copyright and related and neighboring rights to this software to
+// the public domain worldwide. This software is distributed without
+// any warranty.
+//
+// You should have received a copy of the CC0 Public Domain Dedication
+// along with this software.
+// If not, see <http://creativecommons.org/publicdomain/zero/1.0/>.
+//
+
+use syntax::{ast, fold};
+use syntax::parse::token::str_to_ident;
+use syntax::print::pprust;
+use syntax::ptr::P;
+use syntax::util::small_vector::SmallVector;
+
+use mutator::{self, Mutator, SiteCounter, SiteMutator};
+use util;
+
+/// What we know about a binding which VarSwap may use: its declared type,
+/// as source text, and whether it is `mut`
+type Declared = (String, bool);
+
+/// A Mutator which replaces a single use of a variable by another variable
+/// in scope which has the same declared type, e.g. in
+/// `fn clamp(lo: u32, x: u32, hi: u32)` any use of `x` may become `lo`
+/// or `hi`. Without type information we only consider function arguments
+/// and `let`s which are simple names with their types written out, and
+/// only primitive scalar types and shared references, which are `Copy`,
+/// so that the replacement cannot move out of anything. A `mut` variable is
+/// only replaced by another `mut` variable, since it may be assigned to.
+pub struct VarSwap {
+    sites: SiteCounter,
+    /// Every binding in scope, innermost last, with its declaration if it
+    /// is one we can use
+    bindings: Vec<(ast::Name, Option<Declared>)>
+}
+
+impl Mutator for VarSwap {
+    fn rename(&self, name: &str) -> String {
+        format!("_mutate_varswap_{}_{}", self.sites.target(), name)
+    }
+
+    fn did_anything(&self) -> bool {
+        self.sites.did_anything()
+    }
+}
+
+impl SiteMutator for VarSwap {
+    fn at_site(n: usize) -> VarSwap {
+        VarSwap {
+            sites: SiteCounter::new(n),
+            bindings: vec![]
+        }
+    }
+}
+
+/// Whether values of the given type are certainly `Copy`
+fn is_scalar(ty: &ast::Ty) -> bool {
+    match ty.node {
+        ast::Ty_::TyRptr(_, ref mt) => mt.mutbl == ast::Mutability::MutImmutable,
+        ast::Ty_::TyPath(None, ref path) if !path.global && path.segments.len() == 1 => {
+            match &*path.segments[0].identifier.name.as_str() {
+                "bool" | "char" | "f32" | "f64" |
+                "u8" | "u16" | "u32" | "u64" | "usize" |
+                "i8" | "i16" | "i32" | "i64" | "isize" => true,
+                _ => false
+            }
+        },
+        _ => false
+    }
+}
+
+impl VarSwap {
+    /// Brings the bindings of a pattern into scope, with the given type if
+    /// the pattern is a simple name
+    fn bind(&mut self, pat: &P<ast::Pat>, ty: Option<&ast::Ty>) {
+        if let ast::Pat_::PatIdent(ast::BindingMode::BindByValue(mutbl), ref ident, None) = pat.node {
+            if let Some(ty) = ty {
+                if is_scalar(ty) {
+                    let declared = (pprust::ty_to_string(ty), mutbl == ast::Mutability::MutMutable);
+                    self.bindings.push((ident.node.name, Some(declared)));
+                    return;
+                }
+            }
+        }
+        for name in util::pat_bindings(&[pat.clone()]) {
+            self.bindings.push((name, None));
+        }
+    }
+
+    /// The variables which may be used in place of `name`
+    fn replacements(&self, name: ast::Name) -> Vec<ast::Name> {
+        let mut ret = vec![];
+        let mut seen = vec![];
+        let mut target = None;
+        // Find the innermost binding of each name, and of `name` itself
+        for &(other, ref declared) in self.bindings.iter().rev() {
+            if seen.contains(&other) {
+                continue;
+            }
+            seen.push(other);
+            if other == name {
+                target = declared.clone();
+            } else if let Some(ref declared) = *declared {
+                ret.push((other, declared.clone()));
+            }
+        }
+        match target {
+            Some((ty, mutable)) => {
+                ret.into_iter().filter(|&(_, (ref other_ty, other_mutable))|
+                    *other_ty == ty && (other_mutable || !mutable)
+                ).map(|(other, _)| other).collect()
+            },
+            None => vec![]
+        }
+    }
+}
+
+impl fold::Folder for VarSwap {
+    fn fold_item_simple(&mut self, i: ast::Item) -> ast::Item {
+        if let ast::Item_::ItemFn(ref decl, _, _, _, _) = i.node {
+            for arg in decl.inputs.iter() {
+                self.bind(&arg.pat, Some(&arg.ty));
+            }
+        }
+        fold::noop_fold_item_simple(i, self)
+    }
+
+    fn fold_item(&mut self, i: P<ast::Item>) -> SmallVector<P<ast::Item>> {
+        // Nested items cannot see our variables
+        SmallVector::one(i)
+    }
+
+    fn fold_block(&mut self, b: P<ast::Block>) -> P<ast::Block> {
+        let depth = self.bindings.len();
+        let b = fold::noop_fold_block(b, self);
+        self.bindings.truncate(depth);
+        b
+    }
+
+    fn fold_local(&mut self, l: P<ast::Local>) -> P<ast::Local> {
+        // A `let`'s bindings are in scope after it, not in its initializer;
+        // one without an initializer may be assigned to, so we never use it
+        let l = fold::noop_fold_local(l, self);
+        let ty = if l.init.is_some() { l.ty.as_ref().map(|ty| &**ty) } else { None };
+        self.bind(&l.pat, ty);
+        l
+    }
+
+    fn fold_arm(&mut self, arm: ast::Arm) -> ast::Arm {
+        let depth = self.bindings.len();
+        for pat in arm.pats.iter() {
+            self.bind(pat, None);
+        }
+        let arm = fold::noop_fold_arm(arm, self);
+        self.bindings.truncate(depth);
+        arm
+    }
+
+    fn fold_pat(&mut self, p: P<ast::Pat>) -> P<ast::Pat> {
+        // there are no uses of variables in patterns
+        p
+    }
+
+    fn fold_expr(&mut self, e: P<ast::Expr>) -> P<ast::Expr> {
+        if mutator::is_unguarded_loop(&e) {
+            return e;
+        }
+        e.map(|e|
+            match e.node {
+                ast::Expr_::ExprPath(None, mut path) => {
+                    if !path.global && path.segments.len() == 1 {
+                        let name = path.segments[0].identifier.name;
+                        // Each replacement variable is a separate site
+                        for rep in self.replacements(name) {
+                            if self.sites.next() {
+                                path.segments[0].identifier = str_to_ident(rep.as_str());
+                            }
+                        }
+                    }
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprPath(None, path),
+                        span: e.span
+                    }
+                },
+                // The pattern's bindings are only in scope in the body
+                ast::Expr_::ExprIfLet(pat, expr, block, elseexpr) => {
+                    let expr = self.fold_expr(expr);
+                    let depth = self.bindings.len();
+                    self.bind(&pat, None);
+                    let block = self.fold_block(block);
+                    self.bindings.truncate(depth);
+                    let elseexpr = elseexpr.map(|x| self.fold_expr(x));
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprIfLet(pat, expr, block, elseexpr),
+                        span: e.span
+                    }
+                },
+                ast::Expr_::ExprWhileLet(pat, expr, block, label) => {
+                    let expr = self.fold_expr(expr);
+                    let depth = self.bindings.len();
+                    self.bind(&pat, None);
+                    let block = self.fold_block(block);
+                    self.bindings.truncate(depth);
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprWhileLet(pat, expr, block, label),
+                        span: e.span
+                    }
+                },
+                ast::Expr_::ExprForLoop(pat, expr, block, label) => {
+                    let expr = self.fold_expr(expr);
+                    let depth = self.bindings.len();
+                    self.bind(&pat, None);
+                    let block = self.fold_block(block);
+                    self.bindings.truncate(depth);
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprForLoop(pat, expr, block, label),
+                        span: e.span
+                    }
+                },
+                ast::Expr_::ExprClosure(capture, decl, body) => {
+                    let depth = self.bindings.len();
+                    for arg in decl.inputs.iter() {
+                        self.bind(&arg.pat, None);
+                    }
+                    let body = self.fold_block(body);
+                    self.bindings.truncate(depth);
+                    ast::Expr {
+                        id: e.id,
+                        node: ast::Expr_::ExprClosure(capture, decl, body),
+                        span: e.span
+                    }
+                },
+                // Everything else just continue folding
+                _ => fold::noop_fold_expr(e, self)
+            }
+        )
+    }
+
+    fn fold_mac(&mut self, _mac: ast::Mac) -> ast::Mac {
+        // do nothing -- we have to implement this though because the
+        // compiler will yell at us about using a Folder prior to
+        // macro expansion otherwise ("here be dragons")
+        fold::noop_fold_mac(_mac, self)
+    }
+}
+
@@ -14,8 +14,11 @@
 
 //! Utility functions for use in the library
 
-use syntax::ast;
+use std::collections::HashSet;
+use syntax::{ast, codemap, visit};
+use syntax::parse::{self, token};
 use syntax::parse::token::str_to_ident;
+use syntax::ptr::P;
 
 /// Creates a single PathSegment with the given identifier
 pub fn ident_to_pathseg(i: ast::Ident) -> ast::PathSegment {
@@ -33,4 +36,198 @@ pub fn str_to_pathseg(s: &str) -> ast::PathSegment {
     }
 }
 
+/// Creates an expression with a dummy node ID
+pub fn expr(node: ast::Expr_, span: codemap::Span) -> P<ast::Expr> {
+    P(ast::Expr {
+        id: ast::DUMMY_NODE_ID,
+        node: node,
+        span: span
+    })
+}
+
+/// Creates a literal expression
+pub fn lit_expr(lit: ast::Lit_, span: codemap::Span) -> P<ast::Expr> {
+    expr(ast::Expr_::ExprLit(P(codemap::respan(span, lit))), span)
+}
+
+/// Creates a boolean literal expression
+pub fn bool_expr(b: bool, span: codemap::Span) -> P<ast::Expr> {
+    lit_expr(ast::Lit_::LitBool(b), span)
+}
+
+/// Creates an unsuffixed integer literal expression
+pub fn int_expr(n: u64, span: codemap::Span) -> P<ast::Expr> {
+    lit_expr(ast::Lit_::LitInt(n, ast::LitIntType::UnsuffixedIntLit(ast::Sign::Plus)), span)
+}
+
+/// Creates an unsuffixed float literal expression from its source text
+pub fn float_expr(s: &str, span: codemap::Span) -> P<ast::Expr> {
+    lit_expr(ast::Lit_::LitFloatUnsuffixed(token::intern_and_get_ident(s)), span)
+}
+
+/// Creates a string literal expression
+pub fn str_expr(s: &str, span: codemap::Span) -> P<ast::Expr> {
+    lit_expr(ast::Lit_::LitStr(token::intern_and_get_ident(s), ast::StrStyle::CookedStr), span)
+}
+
+/// Creates the block expression `{ &e; rest }`, which evaluates `e` for
+/// its side effects (without moving out of it) and then evaluates to `rest`
+pub fn discard_then(e: P<ast::Expr>, rest: Option<P<ast::Expr>>) -> P<ast::Expr> {
+    let span = e.span;
+    let discard = expr(ast::Expr_::ExprAddrOf(ast::Mutability::MutImmutable, e), span);
+    let block = ast::Block {
+        stmts: vec![P(codemap::respan(span, ast::Stmt_::StmtSemi(discard, ast::DUMMY_NODE_ID)))],
+        expr: rest,
+        id: ast::DUMMY_NODE_ID,
+        rules: ast::BlockCheckMode::DefaultBlock,
+        span: span
+    };
+    expr(ast::Expr_::ExprBlock(P(block)), span)
+}
+
+/// Creates a path expression from the given segments, e.g. `Vec::new`
+pub fn path_expr(segs: &[&str], span: codemap::Span) -> P<ast::Expr> {
+    let path = ast::Path {
+        span: span,
+        global: false,
+        segments: segs.iter().map(|s| str_to_pathseg(s)).collect()
+    };
+    expr(ast::Expr_::ExprPath(None, path), span)
+}
+
+/// Creates a call of the function at the given path, e.g. `Vec::new()`
+pub fn call_expr(segs: &[&str], args: Vec<P<ast::Expr>>, span: codemap::Span) -> P<ast::Expr> {
+    expr(ast::Expr_::ExprCall(path_expr(segs, span), args), span)
+}
+
+/// Parses the arguments of a macro invocation such as `try!(e)` as a
+/// single expression
+pub fn mac_expr(mac: &ast::Mac) -> P<ast::Expr> {
+    let ast::Mac_::MacInvocTT(_, ref tts, _) = mac.node;
+    let sess = parse::new_parse_sess();
+    let mut parser = parse::tts_to_parser(&sess, tts.clone(), vec![]);
+    parser.parse_expr()
+}
+
+/// Creates a block which evaluates to the given expression
+pub fn expr_block(e: P<ast::Expr>) -> P<ast::Block> {
+    P(ast::Block {
+        stmts: vec![],
+        span: e.span,
+        expr: Some(e),
+        id: ast::DUMMY_NODE_ID,
+        rules: ast::BlockCheckMode::DefaultBlock
+    })
+}
+
+/// Returns the `T` and `E` of a function's `Result<T, E>` return type
+pub fn result_types(decl: &ast::FnDecl) -> Option<(P<ast::Ty>, P<ast::Ty>)> {
+    if let ast::FunctionRetTy::Return(ref ty) = decl.output {
+        if let ast::Ty_::TyPath(None, ref path) = ty.node {
+            let seg = path.segments.last().unwrap();
+            if &*seg.identifier.name.as_str() == "Result" {
+                if let ast::PathParameters::AngleBracketedParameters(ref data) = seg.parameters {
+                    if data.types.len() == 2 {
+                        return Some((data.types[0].clone(), data.types[1].clone()));
+                    }
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Visitor which collects the names of the variables bound by a pattern
+struct BindingCollector {
+    names: HashSet<ast::Name>
+}
+
+impl<'v> visit::Visitor<'v> for BindingCollector {
+    fn visit_pat(&mut self, p: &'v ast::Pat) {
+        if let ast::Pat_::PatIdent(_, ref ident, _) = p.node {
+            self.names.insert(ident.node.name);
+        }
+        visit::walk_pat(self, p)
+    }
+
+    fn visit_mac(&mut self, _mac: &'v ast::Mac) {
+        // the default implementation panics
+    }
+}
+
+/// Visitor which collects every single-segment name used in an expression,
+/// including identifiers inside macro invocations
+struct NameCollector {
+    names: HashSet<ast::Name>
+}
+
+impl NameCollector {
+    fn collect_tts(&mut self, tts: &[ast::TokenTree]) {
+        for tt in tts.iter() {
+            match *tt {
+                ast::TokenTree::TtToken(_, token::Token::Ident(ref ident, _)) => {
+                    self.names.insert(ident.name);
+                }
+                ast::TokenTree::TtDelimited(_, ref delimed) => self.collect_tts(&delimed.tts),
+                ast::TokenTree::TtSequence(_, ref seq) => self.collect_tts(&seq.tts),
+                _ => {}
+            }
+        }
+    }
+}
+
+impl<'v> visit::Visitor<'v> for NameCollector {
+    fn visit_path(&mut self, path: &'v ast::Path, _id: ast::NodeId) {
+        if path.segments.len() == 1 {
+            self.names.insert(path.segments[0].identifier.name);
+        }
+        visit::walk_path(self, path)
+    }
+
+    fn visit_mac(&mut self, mac: &'v ast::Mac) {
+        let ast::Mac_::MacInvocTT(_, ref tts, _) = mac.node;
+        self.collect_tts(tts);
+    }
+}
+
+/// Returns the names of all variables bound by a list of patterns. This
+/// is unhygienic and will also return unit variants and constants, since
+/// we cannot distinguish these from bindings before name resolution.
+pub fn pat_bindings(pats: &[P<ast::Pat>]) -> HashSet<ast::Name> {
+    let mut collector = BindingCollector { names: HashSet::new() };
+    for pat in pats.iter() {
+        visit::Visitor::visit_pat(&mut collector, pat);
+    }
+    collector.names
+}
+
+/// Whether an expression mentions any of the given names
+pub fn mentions_any(e: &ast::Expr, names: &HashSet<ast::Name>) -> bool {
+    let mut collector = NameCollector { names: HashSet::new() };
+    visit::Visitor::visit_expr(&mut collector, e);
+    !collector.names.is_disjoint(names)
+}
+
+/// Whether a block mentions any of the given names
+pub fn block_mentions_any(b: &ast::Block, names: &HashSet<ast::Name>) -> bool {
+    let mut collector = NameCollector { names: HashSet::new() };
+    visit::Visitor::visit_block(&mut collector, b);
+    !collector.names.is_disjoint(names)
+}
+
+/// Whether the right-hand side of a `+` or `+=` looks like it is being
+/// appended to a `String`, in which case no other operator will compile.
+/// We have no type information, so this is only a guess.
+pub fn is_string_operand(e: &ast::Expr) -> bool {
+    match e.node {
+        ast::Expr_::ExprAddrOf(_, _) => true,
+        ast::Expr_::ExprLit(ref lit) => {
+            match lit.node {
+                ast::Lit_::LitStr(_, _) => true,
+                _ => false
+            }
+        }
+        _ => false
+    }
+}
 
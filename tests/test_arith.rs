@@ -0,0 +1,37 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn fee(weight: u64, rate: u64) -> u64 {
+    if weight == 0 { 0 } else { weight * rate / 1000 + 1 }
+}
+
+#[mutate]
+pub fn words(n: usize) -> usize {
+    let buf: [usize; 4 * 8] = [n; 4 * 8];
+    buf.len() / n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{fee, words};
+
+    #[test]
+    fn test_zero() {
+        assert_eq!(fee(0, 3), 0);
+    }
+
+    #[test]
+    fn test_nonzero() {
+        assert_eq!(fee(500, 2), 2);
+        assert_eq!(fee(333, 3), 1);
+    }
+
+    #[test]
+    fn test_words() {
+        assert_eq!(words(4), 8);
+    }
+}
+
@@ -0,0 +1,22 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn scale(mut x: u32, k: u32) -> u32 {
+    x *= k;
+    x <<= 1;
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::scale;
+
+    #[test]
+    fn test() {
+        assert_eq!(scale(3, 5), 30);
+    }
+}
+
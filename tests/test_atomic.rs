@@ -0,0 +1,57 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[derive(Debug, PartialEq)]
+pub enum Mode {
+    Acquire,
+    Release
+}
+
+pub struct Gate {
+    pub mode: Mode
+}
+
+impl Gate {
+    pub fn set(&mut self, mode: Mode) {
+        self.mode = mode;
+    }
+}
+
+// The ordering is passed in since weakening it cannot be detected by a
+// single-threaded test
+#[mutate]
+pub fn take_ticket(next: &AtomicUsize, order: Ordering) -> usize {
+    next.fetch_add(1, order)
+}
+
+// `Mode::Release` is not an atomic ordering and must not be weakened,
+// since there is no `Mode::Relaxed`
+#[mutate]
+pub fn open(gate: &mut Gate) {
+    gate.set(Mode::Release);
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use super::{take_ticket, open, Gate, Mode};
+
+    #[test]
+    fn test() {
+        let next = AtomicUsize::new(5);
+        assert_eq!(take_ticket(&next, Ordering::SeqCst), 5);
+        assert_eq!(next.load(Ordering::SeqCst), 6);
+    }
+
+    #[test]
+    fn test_open() {
+        let mut gate = Gate { mode: Mode::Acquire };
+        open(&mut gate);
+        assert_eq!(gate.mode, Mode::Release);
+    }
+}
+
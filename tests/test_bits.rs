@@ -0,0 +1,41 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn flags(a: u8, b: u8) -> u8 {
+    a | b
+}
+
+#[mutate]
+pub fn shift(x: u32) -> u32 {
+    x << 2
+}
+
+#[mutate]
+pub fn slots(n: usize) -> usize {
+    let buf: [usize; 1 << 3] = [n; 1 << 3];
+    buf.len() & n
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flags, shift, slots};
+
+    #[test]
+    fn test_flags() {
+        assert_eq!(flags(3, 5), 7);
+    }
+
+    #[test]
+    fn test_shift() {
+        assert_eq!(shift(1), 4);
+    }
+
+    #[test]
+    fn test_slots() {
+        assert_eq!(slots(12), 8);
+    }
+}
+
@@ -0,0 +1,27 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+pub struct Options {
+    pub strict: bool,
+    pub verbose: bool
+}
+
+#[mutate]
+pub fn defaults() -> Options {
+    Options { strict: true, verbose: false }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::defaults;
+
+    #[test]
+    fn test() {
+        let opts = defaults();
+        assert!(opts.strict);
+        assert!(!opts.verbose);
+    }
+}
+
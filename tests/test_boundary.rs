@@ -0,0 +1,22 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn fits(len: usize, cap: usize) -> bool {
+    len <= cap
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fits;
+
+    #[test]
+    fn test() {
+        assert_eq!(fits(2, 3), true);
+        assert_eq!(fits(3, 3), true);
+        assert_eq!(fits(4, 3), false);
+    }
+}
+
@@ -0,0 +1,20 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn widen(x: i64) -> u64 {
+    x as u64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::widen;
+
+    #[test]
+    fn test() {
+        assert_eq!(widen(0x1_0000_0000), 0x1_0000_0000);
+    }
+}
+
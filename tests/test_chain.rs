@@ -0,0 +1,31 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test(identity_methods(sqrt)))]
+
+#[mutate]
+pub fn backwards(v: &[u32]) -> Vec<u32> {
+    v.iter().rev().cloned().collect()
+}
+
+#[mutate]
+pub fn norm(x: f64, y: f64) -> f64 {
+    (x * x + y * y).sqrt().max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{backwards, norm};
+
+    #[test]
+    fn test_backwards() {
+        assert_eq!(backwards(&[1, 2, 3]), vec![3, 2, 1]);
+    }
+
+    #[test]
+    fn test_norm() {
+        assert_eq!(norm(3.0, 4.0), 5.0);
+        assert_eq!(norm(0.0, 0.0), 1.0);
+    }
+}
+
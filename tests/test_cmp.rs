@@ -0,0 +1,33 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn in_order(a: u32, b: u32) -> bool {
+    a < b
+}
+
+#[mutate]
+pub fn same(x: u32, y: u32) -> bool {
+    x == y
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{in_order, same};
+
+    #[test]
+    fn test_in_order() {
+        assert_eq!(in_order(1, 2), true);
+        assert_eq!(in_order(2, 1), false);
+        assert_eq!(in_order(2, 2), false);
+    }
+
+    #[test]
+    fn test_same() {
+        assert_eq!(same(1, 1), true);
+        assert_eq!(same(1, 2), false);
+    }
+}
+
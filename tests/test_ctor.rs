@@ -0,0 +1,24 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn parse_digit(c: char) -> Result<u32, String> {
+    match c.to_digit(10) {
+        Some(d) => Ok(d),
+        None => Err(String::new())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_digit;
+
+    #[test]
+    fn test() {
+        assert_eq!(parse_digit('9'), Ok(9));
+        assert!(parse_digit('a').is_err());
+    }
+}
+
@@ -0,0 +1,20 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn area(r: f64) -> f64 {
+    3.0 * r * r
+}
+
+#[cfg(test)]
+mod tests {
+    use super::area;
+
+    #[test]
+    fn test() {
+        assert_eq!(area(2.0), 12.0);
+    }
+}
+
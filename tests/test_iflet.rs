@@ -0,0 +1,32 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn first_or_zero(v: &[u32]) -> u32 {
+    if let Some(x) = v.first() { *x } else { 0 }
+}
+
+#[mutate]
+pub fn is_set(o: Option<u32>) -> bool {
+    if let Some(_) = o { true } else { false }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{first_or_zero, is_set};
+
+    #[test]
+    fn test_first() {
+        assert_eq!(first_or_zero(&[5, 6]), 5);
+        assert_eq!(first_or_zero(&[]), 0);
+    }
+
+    #[test]
+    fn test_set() {
+        assert_eq!(is_set(Some(1)), true);
+        assert_eq!(is_set(None), false);
+    }
+}
+
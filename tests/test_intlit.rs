@@ -0,0 +1,43 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn header_len(extra: u32) -> u32 {
+    4 + extra
+}
+
+#[mutate]
+pub fn is_empty(n: u32) -> bool {
+    n == 0
+}
+
+#[mutate]
+pub fn top_bit(x: u32) -> u32 {
+    x >> 31
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{header_len, is_empty, top_bit};
+
+    #[test]
+    fn test_header_len() {
+        assert_eq!(header_len(0), 4);
+        assert_eq!(header_len(1), 5);
+    }
+
+    #[test]
+    fn test_is_empty() {
+        assert_eq!(is_empty(0), true);
+        assert_eq!(is_empty(1), false);
+    }
+
+    #[test]
+    fn test_top_bit() {
+        assert_eq!(top_bit(0x80000000), 1);
+        assert_eq!(top_bit(1), 0);
+    }
+}
+
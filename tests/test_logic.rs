@@ -0,0 +1,22 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn both(a: bool, b: bool) -> bool {
+    if a && b { true } else { false }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::both;
+
+    #[test]
+    fn test() {
+        assert_eq!(both(true, true), true);
+        assert_eq!(both(true, false), false);
+        assert_eq!(both(false, true), false);
+    }
+}
+
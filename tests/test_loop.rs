@@ -0,0 +1,28 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test(guard_loops))]
+
+#[mutate]
+pub fn sum_to(n: u32) -> u32 {
+    let mut total = 0;
+    let mut i = 0;
+    while i < n {
+        i += 1;
+        total += i;
+    }
+    total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sum_to;
+
+    #[test]
+    fn test() {
+        assert_eq!(sum_to(0), 0);
+        assert_eq!(sum_to(3), 6);
+        assert_eq!(sum_to(4), 10);
+    }
+}
+
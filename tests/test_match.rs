@@ -0,0 +1,28 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn bucket(n: u32) -> u32 {
+    match n {
+        0 => 10,
+        x if x > 100 => 30,
+        _ => 20
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bucket;
+
+    #[test]
+    fn test() {
+        assert_eq!(bucket(0), 10);
+        assert_eq!(bucket(5), 20);
+        assert_eq!(bucket(100), 20);
+        assert_eq!(bucket(101), 30);
+        assert_eq!(bucket(200), 30);
+    }
+}
+
@@ -0,0 +1,44 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test(swap_methods(checked_div = "checked_rem"),
+                                 identity_methods(to_ascii_uppercase)))]
+
+use std::ascii::AsciiExt;
+
+#[mutate]
+pub fn smallest(v: &[u32]) -> Option<u32> {
+    v.iter().cloned().min()
+}
+
+#[mutate]
+pub fn per_item(total: u32, n: u32) -> Option<u32> {
+    total.checked_div(n)
+}
+
+#[mutate]
+pub fn is_yes(s: &str) -> bool {
+    s.trim().to_ascii_uppercase().starts_with("Y")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{smallest, per_item, is_yes};
+
+    #[test]
+    fn test_smallest() {
+        assert_eq!(smallest(&[3, 2, 5]), Some(2));
+    }
+
+    #[test]
+    fn test_per_item() {
+        assert_eq!(per_item(10, 3), Some(3));
+    }
+
+    #[test]
+    fn test_is_yes() {
+        assert_eq!(is_yes(" yes"), true);
+        assert_eq!(is_yes("no"), false);
+    }
+}
+
@@ -0,0 +1,45 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn credit(balance: u8, amount: u8) -> u8 {
+    balance.saturating_add(amount)
+}
+
+#[mutate]
+pub fn next_id(id: u8) -> u8 {
+    id + 1u8
+}
+
+#[mutate]
+pub fn padded(n: usize) -> usize {
+    let buf: [usize; 4 + 4usize] = [n; 4 + 4usize];
+    buf.len() - n
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+    use super::{credit, next_id, padded};
+
+    #[test]
+    fn test_credit() {
+        assert_eq!(credit(1, 2), 3);
+        assert_eq!(credit(250, 10), 255);
+    }
+
+    #[test]
+    fn test_next_id() {
+        assert_eq!(next_id(1), 2);
+        // overflow panics in a test build
+        assert!(thread::spawn(|| next_id(255)).join().is_err());
+    }
+
+    #[test]
+    fn test_padded() {
+        assert_eq!(padded(2), 6);
+    }
+}
+
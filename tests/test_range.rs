@@ -0,0 +1,20 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn middle(v: &[u32]) -> &[u32] {
+    &v[1..v.len() - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::middle;
+
+    #[test]
+    fn test() {
+        assert_eq!(middle(&[1, 2, 3, 4]), &[2, 3][..]);
+    }
+}
+
@@ -0,0 +1,30 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn describe(n: u32) -> Option<String> {
+    Some(n.to_string())
+}
+
+#[mutate]
+pub fn count_words(s: &str) -> usize {
+    s.split(' ').count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{describe, count_words};
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(describe(7), Some("7".to_string()));
+    }
+
+    #[test]
+    fn test_count_words() {
+        assert_eq!(count_words("a b c"), 3);
+    }
+}
+
@@ -0,0 +1,29 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+pub struct Counter {
+    pub count: u32,
+    pub log: Vec<u32>
+}
+
+#[mutate]
+pub fn bump(c: &mut Counter, n: u32) {
+    c.count += n;
+    c.log.push(n);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{bump, Counter};
+
+    #[test]
+    fn test() {
+        let mut c = Counter { count: 1, log: vec![] };
+        bump(&mut c, 3);
+        assert_eq!(c.count, 4);
+        assert_eq!(c.log, vec![3]);
+    }
+}
+
@@ -0,0 +1,21 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn separator(last: bool) -> &'static str {
+    if last { "" } else { ", " }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::separator;
+
+    #[test]
+    fn test() {
+        assert_eq!(separator(false), ", ");
+        assert_eq!(separator(true), "");
+    }
+}
+
@@ -0,0 +1,36 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+use std::num::ParseIntError;
+
+#[mutate]
+pub fn double(s: &str) -> Result<u32, ParseIntError> {
+    let n: u32 = try!(s.parse());
+    Ok(n * 2)
+}
+
+#[mutate]
+pub fn check(s: &str) -> Result<(), ParseIntError> {
+    try!(s.parse::<u32>());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{double, check};
+
+    #[test]
+    fn test_double() {
+        assert_eq!(double("3"), Ok(6));
+        assert!(double("x").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check("3").is_ok());
+        assert!(check("x").is_err());
+    }
+}
+
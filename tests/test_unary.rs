@@ -0,0 +1,51 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn negate_if(x: i32, flip: bool) -> i32 {
+    if flip { -x } else { x }
+}
+
+#[mutate]
+pub fn same_sign(a: i32, b: i32) -> bool {
+    let ok = (a < 0) == (b < 0);
+    ok
+}
+
+#[mutate]
+pub fn sign_name(x: i32) -> &'static str {
+    match x {
+        -1 => "minus",
+        1 => "plus",
+        _ => "other"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{negate_if, same_sign, sign_name};
+
+    #[test]
+    fn test_negate_if() {
+        assert_eq!(negate_if(2, true), -2);
+        assert_eq!(negate_if(2, false), 2);
+    }
+
+    #[test]
+    fn test_same_sign() {
+        assert_eq!(same_sign(1, 2), true);
+        assert_eq!(same_sign(-1, 2), false);
+        assert_eq!(same_sign(0, 1), true);
+        assert_eq!(same_sign(1, 0), true);
+    }
+
+    #[test]
+    fn test_sign_name() {
+        assert_eq!(sign_name(-1), "minus");
+        assert_eq!(sign_name(1), "plus");
+        assert_eq!(sign_name(0), "other");
+    }
+}
+
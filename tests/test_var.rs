@@ -0,0 +1,20 @@
+#![feature(custom_attribute, plugin)]
+
+#![plugin(halfsleep)]
+#![cfg_attr(test, mutation_test)]
+
+#[mutate]
+pub fn width(lo: u32, hi: u32) -> u32 {
+    hi - lo
+}
+
+#[cfg(test)]
+mod tests {
+    use super::width;
+
+    #[test]
+    fn test() {
+        assert_eq!(width(3, 10), 7);
+    }
+}
+